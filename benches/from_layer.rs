@@ -0,0 +1,356 @@
+// Benchmarks of the different ways of reading a layer, mostly using the countries fixture.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use gdal::vector::{Feature, FieldValue, Geometry, LayerAccess, OGRFieldType, OGRwkbGeometryType};
+use gdal::Dataset;
+use gdal_traits::*;
+
+const COUNTRIES: &str = "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp";
+const GAVLE: &str = "fixtures/Gävle_test_2_Geopackage_530541/Gävle_test_2_530541.gpkg";
+
+/// Only the FID of each feature.
+#[allow(dead_code)]
+struct Fid(Option<u64>);
+
+impl FromFeature<0, GdalTraitError> for Fid {
+    const FIELDS: [&'static str; Self::NUM_FIELDS] = [];
+
+    fn read(
+        fid: Option<u64>,
+        _fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+        _geometry: Option<&Geometry>,
+    ) -> Result<Self, GdalTraitError> {
+        Ok(Self(fid))
+    }
+}
+
+/// A feature of the generated list-heavy layer, see [`bench_lazy`].
+#[allow(dead_code)]
+struct Track {
+    kind: i32,
+    samples: Option<Vec<f64>>,
+}
+
+impl FromFeature<2, GdalTraitError> for Track {
+    const FIELDS: [&'static str; Self::NUM_FIELDS] = ["KIND", "SAMPLES"];
+
+    fn read(
+        _fid: Option<u64>,
+        fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+        _geometry: Option<&Geometry>,
+    ) -> Result<Self, GdalTraitError> {
+        let [kind_field, samples_field] = fields;
+        let kind = kind_field.try_into_int()?;
+
+        Ok(Self {
+            kind,
+            samples: (kind == 0)
+                .then(|| samples_field.try_into_real_list())
+                .transpose()?,
+        })
+    }
+
+    // Only read the samples of the features which keep them.
+    fn read_lazy(
+        _fid: Option<u64>,
+        fields: LazyFields<'_, { Self::NUM_FIELDS }>,
+        _geometry: Option<&Geometry>,
+    ) -> Result<Self, GdalTraitError> {
+        let kind = fields.get(0).try_into_int()?;
+
+        Ok(Self {
+            kind,
+            samples: (kind == 0)
+                .then(|| fields.get(1).try_into_real_list())
+                .transpose()?,
+        })
+    }
+}
+
+/// Fields which are never NULL in the fixture, so readable by `from_layer_assume_present`.
+#[allow(dead_code)]
+struct RequiredCountry {
+    name: String,
+    iso_a3: String,
+    continent: String,
+    pop_est: f64,
+    pop_year: i32,
+}
+
+impl FromFeature<5, GdalTraitError> for RequiredCountry {
+    const FIELDS: [&'static str; Self::NUM_FIELDS] =
+        ["NAME", "ISO_A3", "CONTINENT", "POP_EST", "POP_YEAR"];
+
+    fn read(
+        _fid: Option<u64>,
+        fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+        _geometry: Option<&Geometry>,
+    ) -> Result<Self, GdalTraitError> {
+        let [name_field, iso_a3_field, continent_field, pop_est_field, pop_year_field] = fields;
+
+        Ok(Self {
+            name: name_field.try_into_string()?,
+            iso_a3: iso_a3_field.try_into_string()?,
+            continent: continent_field.try_into_string()?,
+            pop_est: pop_est_field.try_into_real()?,
+            pop_year: pop_year_field.try_into_int()?,
+        })
+    }
+}
+
+/// [`RequiredCountry`], with the other fields read too.
+#[allow(dead_code)]
+struct EagerCountry(RequiredCountry);
+
+impl FromFeature<5, GdalTraitError> for EagerCountry {
+    const FIELDS: [&'static str; Self::NUM_FIELDS] = RequiredCountry::FIELDS;
+    const IGNORE_OTHER_FIELDS: bool = false;
+
+    fn read(
+        fid: Option<u64>,
+        fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+        geometry: Option<&Geometry>,
+    ) -> Result<Self, GdalTraitError> {
+        RequiredCountry::read(fid, fields, geometry).map(Self)
+    }
+}
+
+// Number of passes over the layer per iteration, to scale up the small fixture.
+const PASSES: usize = 20;
+
+fn bench_assume_present(c: &mut Criterion) {
+    let ds = Dataset::open(COUNTRIES).unwrap();
+    let mut layer = ds.layer(0).unwrap();
+
+    let mut group = c.benchmark_group("assume_present");
+    group.bench_function("from_layer", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                RequiredCountry::from_layer(&mut layer).unwrap();
+            }
+        })
+    });
+    group.bench_function("from_layer_assume_present", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                RequiredCountry::from_layer_assume_present(&mut layer).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_lazy(c: &mut Criterion) {
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut ds = driver.create_vector_only("").unwrap();
+    let layer = create_layer(
+        &mut ds,
+        "tracks",
+        None,
+        OGRwkbGeometryType::wkbNone,
+        &[
+            FieldDefinition::new("KIND", OGRFieldType::OFTInteger),
+            FieldDefinition::new("SAMPLES", OGRFieldType::OFTRealList),
+        ],
+    )
+    .unwrap();
+
+    // 1000 features with 1000 samples each, of which only every 10th feature keeps them.
+    let samples: Vec<f64> = (0..1000).map(f64::from).collect();
+    for i in 0..1000 {
+        let mut feature = Feature::new(layer.defn()).unwrap();
+        feature
+            .set_field(0, &FieldValue::IntegerValue(i % 10))
+            .unwrap();
+        feature
+            .set_field(1, &FieldValue::RealListValue(samples.clone()))
+            .unwrap();
+        feature.create(&layer).unwrap();
+    }
+    let mut layer = ds.layer(0).unwrap();
+
+    let mut group = c.benchmark_group("lazy");
+    group.bench_function("from_layer", |b| {
+        b.iter(|| Track::from_layer(&mut layer).unwrap())
+    });
+    group.bench_function("from_layer_lazy", |b| {
+        b.iter(|| Track::from_layer_lazy(&mut layer).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_field_array(c: &mut Criterion) {
+    let ds = Dataset::open(COUNTRIES).unwrap();
+    let mut layer = ds.layer(0).unwrap();
+    let indices: Vec<usize> = RequiredCountry::FIELDS
+        .iter()
+        .map(|name| layer.defn().field_index(name).unwrap())
+        .collect();
+
+    // The former collection into a Vec, converted into an array, against filling it in place.
+    let mut group = c.benchmark_group("field_array");
+    group.bench_function("vec_try_into", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                for feature in layer.features() {
+                    let fields: Vec<FieldResult<GdalTraitError>> = indices
+                        .iter()
+                        .map(|idx| feature.field(*idx).into())
+                        .collect();
+                    let fields: [FieldResult<GdalTraitError>; 5] = fields.try_into().unwrap();
+                    RequiredCountry::read(feature.fid(), fields, None).unwrap();
+                }
+            }
+        })
+    });
+    group.bench_function("array_from_fn", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                for feature in layer.features() {
+                    let fields: [FieldResult<GdalTraitError>; 5] =
+                        std::array::from_fn(|i| feature.field(indices[i]).into());
+                    RequiredCountry::read(feature.fid(), fields, None).unwrap();
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_geometries(c: &mut Criterion) {
+    let ds = Dataset::open(COUNTRIES).unwrap();
+    let mut layer = ds.layer(0).unwrap();
+
+    // The fixture has 168 fields, which geometries_from_layer has the driver skip.
+    let mut group = c.benchmark_group("geometries");
+    group.bench_function("features", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                layer
+                    .features()
+                    .filter_map(|feature| feature.geometry().cloned())
+                    .collect::<Vec<Geometry>>();
+            }
+        })
+    });
+    group.bench_function("geometries_from_layer", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                geometries_from_layer(&mut layer).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_ignored_fields(c: &mut Criterion) {
+    let ds = Dataset::open(COUNTRIES).unwrap();
+    let mut layer = ds.layer(0).unwrap();
+
+    // The same reader, having the driver skip all but 5 of the 168 fields or reading them all.
+    let mut group = c.benchmark_group("ignored_fields");
+    group.bench_function("ignored", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                RequiredCountry::from_layer(&mut layer).unwrap();
+            }
+        })
+    });
+    group.bench_function("read", |b| {
+        b.iter(|| {
+            for _ in 0..PASSES {
+                EagerCountry::from_layer(&mut layer).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_from_fids(c: &mut Criterion) {
+    let ds = Dataset::open(COUNTRIES).unwrap();
+    let mut shapefile = ds.layer(0).unwrap();
+
+    // The same features as GeoJSONSeq, which has no random reads.
+    let lines: Vec<String> = shapefile
+        .features()
+        .map(|feature| {
+            let properties: serde_json::Map<String, serde_json::Value> = RequiredCountry::FIELDS
+                .iter()
+                .map(|name| {
+                    let idx = feature.field_index(name).unwrap();
+                    let value = match feature.field(idx).unwrap().unwrap() {
+                        FieldValue::StringValue(s) => s.into(),
+                        FieldValue::RealValue(v) => v.into(),
+                        FieldValue::IntegerValue(v) => v.into(),
+                        value => panic!("Unexpected {value:?}"),
+                    };
+                    (name.to_string(), value)
+                })
+                .collect();
+            let geometry: serde_json::Value =
+                serde_json::from_str(&feature.geometry().unwrap().json().unwrap()).unwrap();
+            serde_json::json!({
+                "type": "Feature",
+                "properties": properties,
+                "geometry": geometry,
+            })
+            .to_string()
+        })
+        .collect();
+    gdal::vsi::create_mem_file("/vsimem/countries.geojsonl", lines.join("\n").into_bytes())
+        .unwrap();
+    let seq_ds = Dataset::open("/vsimem/countries.geojsonl").unwrap();
+    let mut sequential = seq_ds.layer(0).unwrap();
+
+    let fids: Vec<u64> = shapefile
+        .features()
+        .filter_map(|f| f.fid())
+        .step_by(4)
+        .collect();
+    let seq_fids: Vec<u64> = sequential
+        .features()
+        .filter_map(|f| f.fid())
+        .step_by(4)
+        .collect();
+    let seq_fids_unsorted: Vec<u64> = seq_fids.iter().rev().copied().collect();
+
+    let gpkg_ds = Dataset::open(GAVLE).unwrap();
+    let mut gpkg = gpkg_ds.layer_by_name("Net_VAG_Link").unwrap();
+    let gpkg_fids: Vec<u64> = gpkg.features().filter_map(|f| f.fid()).step_by(4).collect();
+    let gpkg_fids_unsorted: Vec<u64> = gpkg_fids.iter().rev().copied().collect();
+
+    // Ascending FIDs are read in a single pass without random reads, otherwise one by one. The
+    // GeoPackage has random reads, so is fetched one by one either way.
+    let mut group = c.benchmark_group("from_fids");
+    group.bench_function("shapefile", |b| {
+        b.iter(|| RequiredCountry::from_fids(&mut shapefile, &fids).unwrap())
+    });
+    group.bench_function("geojsonseq_ascending", |b| {
+        b.iter(|| RequiredCountry::from_fids(&mut sequential, &seq_fids).unwrap())
+    });
+    group.bench_function("geojsonseq_unsorted", |b| {
+        b.iter(|| RequiredCountry::from_fids(&mut sequential, &seq_fids_unsorted).unwrap())
+    });
+    group.bench_function("geopackage_ascending", |b| {
+        b.iter(|| Fid::from_fids(&mut gpkg, &gpkg_fids).unwrap())
+    });
+    group.bench_function("geopackage_unsorted", |b| {
+        b.iter(|| Fid::from_fids(&mut gpkg, &gpkg_fids_unsorted).unwrap())
+    });
+    group.finish();
+
+    drop(sequential);
+    drop(seq_ds);
+    gdal::vsi::unlink_mem_file("/vsimem/countries.geojsonl").unwrap();
+}
+
+criterion_group!(
+    benches,
+    bench_lazy,
+    bench_assume_present,
+    bench_field_array,
+    bench_geometries,
+    bench_ignored_fields,
+    bench_from_fids
+);
+criterion_main!(benches);
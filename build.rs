@@ -0,0 +1,22 @@
+use std::env;
+
+// Expose the GDAL version found by gdal-sys as `gdal_ge_3_<minor>` cfgs, for gating helpers on
+// functions which only exist in newer GDAL versions.
+const MAX_MINOR: i64 = 20;
+
+fn main() {
+    let version = env::var("DEP_GDAL_VERSION_NUMBER")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(0);
+    let major = version / 1_000_000;
+    let minor = (version - major * 1_000_000) / 10_000;
+
+    for m in 0..=MAX_MINOR {
+        println!("cargo::rustc-check-cfg=cfg(gdal_ge_3_{m})");
+
+        if major > 3 || (major == 3 && minor >= m) {
+            println!("cargo::rustc-cfg=gdal_ge_3_{m}");
+        }
+    }
+}
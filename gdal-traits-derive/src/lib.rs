@@ -0,0 +1,107 @@
+//! Derive macros for `gdal-traits`, re-exported from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, LitStr, Type};
+
+/// Implements `FromDataset` for a struct of `Vec<T: FromFeature>` fields, each read from its own
+/// layer given by `#[gdal(layer = "name")]` or `#[gdal(layer_index = N)]`.
+///
+/// The error type defaults to `GdalTraitError`, and is set with `#[gdal(error = MyError)]` on the
+/// struct.
+#[proc_macro_derive(FromDataset, attributes(gdal))]
+pub fn derive_from_dataset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_dataset(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn from_dataset(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let mut error: Option<Type> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("gdal"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("error") {
+                error = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `error`"))
+            }
+        })?;
+    }
+    let error = error.unwrap_or_else(|| syn::parse_quote!(::gdal_traits::GdalTraitError));
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "FromDataset can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "FromDataset can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut reads = Vec::with_capacity(fields.len());
+    for field in fields {
+        let mut layer = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("gdal"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let value = if meta.path.is_ident("layer") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    quote!(::gdal_traits::DatasetLayer::Name(::std::string::String::from(#name)))
+                } else if meta.path.is_ident("layer_index") {
+                    let index: LitInt = meta.value()?.parse()?;
+                    let index: usize = index.base10_parse()?;
+                    quote!(::gdal_traits::DatasetLayer::Index(#index))
+                } else {
+                    return Err(meta.error("expected `layer` or `layer_index`"));
+                };
+                if layer.is_some() {
+                    return Err(meta.error("layer given more than once"));
+                }
+                layer = Some(value);
+                Ok(())
+            })?;
+        }
+        let layer = layer.ok_or_else(|| {
+            Error::new_spanned(
+                field,
+                "missing `#[gdal(layer = \"...\")]` or `#[gdal(layer_index = ...)]`",
+            )
+        })?;
+
+        let ident = &field.ident;
+        reads.push(quote! {
+            #ident: ::gdal_traits::__private::read_dataset_layer(dataset, &#layer)?
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::gdal_traits::FromDataset<#error> for #name #ty_generics #where_clause {
+            fn from_open_dataset(
+                dataset: &::gdal_traits::__private::Dataset,
+            ) -> ::core::result::Result<Self, #error> {
+                ::core::result::Result::Ok(Self { #(#reads,)* })
+            }
+        }
+    })
+}
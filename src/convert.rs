@@ -0,0 +1,494 @@
+use gdal::vector::FieldValue;
+
+use crate::{FieldResult, GdalTraitError};
+
+// Conversions of string fields into domain types.
+impl FieldResult<GdalTraitError> {
+    /// Attempt to parse a string `FieldResult` into RGBA color components.
+    ///
+    /// Accepts `#RGB`, `#RRGGBB`, `#RRGGBBAA`, `rgb(r, g, b)` and `rgba(r, g, b, a)`, where the
+    /// alpha of `rgba(...)` is given in the range 0.0-1.0. Alpha defaults to 255 when absent.
+    pub fn try_into_rgba(&self) -> Result<[u8; 4], GdalTraitError> {
+        let color = self.as_str()?;
+        parse_rgba(color.trim()).ok_or_else(|| {
+            GdalTraitError::InvalidFieldValue(format!("Failed to parse {color:?} as a color"))
+        })
+    }
+
+    /// Attempt to parse a string `FieldResult` into Option<...> RGBA color components.
+    pub fn try_into_rgba_opt(&self) -> Result<Option<[u8; 4]>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_rgba().map(Some),
+        }
+    }
+}
+
+// Conversions of numeric fields into domain values.
+impl FieldResult<GdalTraitError> {
+    /// Attempt to convert a real `FieldResult` holding a percentage into a 0.0-1.0 fraction.
+    ///
+    /// Datasets store percentages both as 0-100 and as 0-1, so values above 1.0 are assumed to
+    /// be on the 0-100 scale and divided by 100. Note that this makes 1.0 ambiguous; it is
+    /// always read as a fraction, i.e. 100%.
+    pub fn try_into_fraction(&self) -> Result<f64, GdalTraitError> {
+        let value = self.try_into_real()?;
+        Ok(if value > 1.0 { value / 100.0 } else { value })
+    }
+
+    /// Attempt to convert a real `FieldResult` holding a percentage into an Option<...> 0.0-1.0
+    /// fraction.
+    pub fn try_into_fraction_opt(&self) -> Result<Option<f64>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_fraction().map(Some),
+        }
+    }
+
+    /// Like [`FieldResult::try_into_fraction`], but errors for values outside of 0-100,
+    /// including negative and NaN values.
+    pub fn try_into_fraction_strict(&self) -> Result<f64, GdalTraitError> {
+        let value = self.try_into_real()?;
+        if !(0.0..=100.0).contains(&value) {
+            return Err(GdalTraitError::InvalidFieldValue(format!(
+                "Percentage {value} is outside of 0-100"
+            )));
+        }
+        self.try_into_fraction()
+    }
+
+    /// Like [`FieldResult::try_into_fraction_opt`], but errors for values outside of 0-100.
+    pub fn try_into_fraction_strict_opt(&self) -> Result<Option<f64>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_fraction_strict().map(Some),
+        }
+    }
+
+    /// Attempt to convert an integer `FieldResult` holding a bitmask into its raw bits.
+    ///
+    /// Accepts both 32 and 64-bit integers. Negative values are reinterpreted rather than sign
+    /// extended, so a 32-bit `-1` gives `0xFFFF_FFFF`.
+    pub fn try_into_bits(&self) -> Result<u64, GdalTraitError> {
+        match self.to_owned().into_res()? {
+            FieldValue::IntegerValue(v) => Ok(u64::from(v as u32)),
+            FieldValue::Integer64Value(v) => Ok(v as u64),
+            value => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to convert {value:?} into bits"
+            ))),
+        }
+    }
+
+    /// Attempt to convert an integer `FieldResult` holding a bitmask into Option<...> raw bits.
+    pub fn try_into_bits_opt(&self) -> Result<Option<u64>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_bits().map(Some),
+        }
+    }
+}
+
+#[cfg(feature = "bitflags")]
+impl FieldResult<GdalTraitError> {
+    /// Attempt to convert an integer `FieldResult` holding a bitmask into a [`bitflags::Flags`]
+    /// value.
+    ///
+    /// Errors if any bit is set which doesn't correspond to a defined flag.
+    pub fn try_into_flags<T>(&self) -> Result<T, GdalTraitError>
+    where
+        T: bitflags::Flags,
+        T::Bits: TryFrom<u64>,
+    {
+        let bits = self.try_into_bits()?;
+        T::Bits::try_from(bits)
+            .ok()
+            .and_then(T::from_bits)
+            .ok_or_else(|| {
+                GdalTraitError::InvalidFieldValue(format!(
+                    "Bits {bits:#x} don't correspond to defined flags"
+                ))
+            })
+    }
+
+    /// Attempt to convert an integer `FieldResult` holding a bitmask into an Option<...>
+    /// [`bitflags::Flags`] value.
+    pub fn try_into_flags_opt<T>(&self) -> Result<Option<T>, GdalTraitError>
+    where
+        T: bitflags::Flags,
+        T::Bits: TryFrom<u64>,
+    {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_flags().map(Some),
+        }
+    }
+}
+
+// Coercions for mixed-type columns, e.g. of CSV-derived layers, where a column typed as string
+// holds numbers or the other way around. Each tries, in order:
+//
+// 1. the target type itself,
+// 2. lossless conversions from the other numeric types, e.g. 32 into 64-bit integers,
+// 3. parsing strings, ignoring surrounding whitespace, or formatting numbers into strings.
+//
+// Reals are never truncated into integers. The `_opt` variants also read empty or whitespace-only
+// strings as `None`, as empty CSV cells are.
+impl FieldResult<GdalTraitError> {
+    /// Attempt to coerce a `FieldResult` into an i32, see the coercion order above.
+    pub fn try_into_int_coerce(&self) -> Result<i32, GdalTraitError> {
+        match coerce_source(self)? {
+            FieldValue::IntegerValue(v) => Some(*v),
+            FieldValue::Integer64Value(v) => i32::try_from(*v).ok(),
+            FieldValue::StringValue(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| coerce_error(self, "i32"))
+    }
+
+    /// Attempt to coerce a `FieldResult` into an Option<i32>.
+    pub fn try_into_int_coerce_opt(&self) -> Result<Option<i32>, GdalTraitError> {
+        coerce_opt(self, Self::try_into_int_coerce)
+    }
+
+    /// Attempt to coerce a `FieldResult` into an i64, see the coercion order above.
+    pub fn try_into_int64_coerce(&self) -> Result<i64, GdalTraitError> {
+        match coerce_source(self)? {
+            FieldValue::Integer64Value(v) => Some(*v),
+            FieldValue::IntegerValue(v) => Some(i64::from(*v)),
+            FieldValue::StringValue(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| coerce_error(self, "i64"))
+    }
+
+    /// Attempt to coerce a `FieldResult` into an Option<i64>.
+    pub fn try_into_int64_coerce_opt(&self) -> Result<Option<i64>, GdalTraitError> {
+        coerce_opt(self, Self::try_into_int64_coerce)
+    }
+
+    /// Attempt to coerce a `FieldResult` into an f64, see the coercion order above.
+    ///
+    /// 64-bit integers beyond 2^53 lose precision.
+    pub fn try_into_real_coerce(&self) -> Result<f64, GdalTraitError> {
+        match coerce_source(self)? {
+            FieldValue::RealValue(v) => Some(*v),
+            FieldValue::IntegerValue(v) => Some(f64::from(*v)),
+            FieldValue::Integer64Value(v) => Some(*v as f64),
+            FieldValue::StringValue(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| coerce_error(self, "f64"))
+    }
+
+    /// Attempt to coerce a `FieldResult` into an Option<f64>.
+    pub fn try_into_real_coerce_opt(&self) -> Result<Option<f64>, GdalTraitError> {
+        coerce_opt(self, Self::try_into_real_coerce)
+    }
+
+    /// Attempt to coerce a `FieldResult` into a String, formatting integers and reals.
+    ///
+    /// Strings are kept as they are, including surrounding whitespace. Errors for other values,
+    /// e.g. lists and dates.
+    pub fn try_into_string_coerce(&self) -> Result<String, GdalTraitError> {
+        match coerce_source(self)? {
+            FieldValue::StringValue(s) => Ok(s.clone()),
+            FieldValue::IntegerValue(v) => Ok(v.to_string()),
+            FieldValue::Integer64Value(v) => Ok(v.to_string()),
+            FieldValue::RealValue(v) => Ok(v.to_string()),
+            _ => Err(coerce_error(self, "String")),
+        }
+    }
+
+    /// Attempt to coerce a `FieldResult` into an Option<String>.
+    ///
+    /// Unlike the numeric coercions, empty strings are kept.
+    pub fn try_into_string_coerce_opt(&self) -> Result<Option<String>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_string_coerce().map(Some),
+        }
+    }
+}
+
+fn coerce_source(field: &FieldResult<GdalTraitError>) -> Result<&FieldValue, GdalTraitError> {
+    match field {
+        FieldResult::Some(value) => Ok(value),
+        FieldResult::Null => Err(GdalTraitError::NullField),
+        FieldResult::Error(e) => Err(e.clone()),
+    }
+}
+
+fn coerce_opt<T>(
+    field: &FieldResult<GdalTraitError>,
+    coerce: impl FnOnce(&FieldResult<GdalTraitError>) -> Result<T, GdalTraitError>,
+) -> Result<Option<T>, GdalTraitError> {
+    match field {
+        FieldResult::Null => Ok(None),
+        FieldResult::Some(FieldValue::StringValue(s)) if s.trim().is_empty() => Ok(None),
+        _ => coerce(field).map(Some),
+    }
+}
+
+fn coerce_error(field: &FieldResult<GdalTraitError>, type_name: &str) -> GdalTraitError {
+    GdalTraitError::InvalidFieldValue(format!("Failed to coerce {field:?} into a {type_name}"))
+}
+
+fn parse_rgba(color: &str) -> Option<[u8; 4]> {
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |i: usize, len: usize| u8::from_str_radix(hex.get(i..i + len)?, 16).ok();
+        return match hex.len() {
+            // Expand e.g. `f` into `ff`.
+            3 => Some([
+                channel(0, 1)? * 17,
+                channel(1, 1)? * 17,
+                channel(2, 1)? * 17,
+                255,
+            ]),
+            6 => Some([channel(0, 2)?, channel(2, 2)?, channel(4, 2)?, 255]),
+            8 => Some([
+                channel(0, 2)?,
+                channel(2, 2)?,
+                channel(4, 2)?,
+                channel(6, 2)?,
+            ]),
+            _ => None,
+        };
+    }
+
+    let (args, has_alpha) = if let Some(args) = color.strip_prefix("rgba(") {
+        (args.strip_suffix(')')?, true)
+    } else {
+        (color.strip_prefix("rgb(")?.strip_suffix(')')?, false)
+    };
+
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    match (parts.as_slice(), has_alpha) {
+        ([r, g, b], false) => Some([r.parse().ok()?, g.parse().ok()?, b.parse().ok()?, 255]),
+        ([r, g, b, a], true) => {
+            let alpha: f64 = a.parse().ok()?;
+            if !(0.0..=1.0).contains(&alpha) {
+                return None;
+            }
+            Some([
+                r.parse().ok()?,
+                g.parse().ok()?,
+                b.parse().ok()?,
+                (alpha * 255.0).round() as u8,
+            ])
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string_field(s: &str) -> FieldResult<GdalTraitError> {
+        FieldResult::Some(FieldValue::StringValue(s.to_string()))
+    }
+
+    #[test]
+    fn test_rgba() {
+        assert_eq!(
+            string_field("#ff8000").try_into_rgba().unwrap(),
+            [255, 128, 0, 255]
+        );
+        assert_eq!(
+            string_field("#F80").try_into_rgba().unwrap(),
+            [255, 136, 0, 255]
+        );
+        assert_eq!(
+            string_field("#ff800080").try_into_rgba().unwrap(),
+            [255, 128, 0, 128]
+        );
+        assert_eq!(
+            string_field("rgb(10, 20, 30)").try_into_rgba().unwrap(),
+            [10, 20, 30, 255]
+        );
+        assert_eq!(
+            string_field("rgba(10,20,30,0.5)").try_into_rgba().unwrap(),
+            [10, 20, 30, 128]
+        );
+
+        assert!(matches!(
+            string_field("rgb(300, 0, 0)").try_into_rgba(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+        assert!(string_field("#12345").try_into_rgba().is_err());
+        assert!(string_field("red").try_into_rgba().is_err());
+
+        let null: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(null.try_into_rgba_opt().unwrap(), None);
+    }
+
+    #[test]
+    fn test_fraction() {
+        let real = |v: f64| FieldResult::<GdalTraitError>::Some(FieldValue::RealValue(v));
+
+        assert_eq!(real(0.25).try_into_fraction().unwrap(), 0.25);
+        assert_eq!(real(25.0).try_into_fraction().unwrap(), 0.25);
+        assert_eq!(real(1.0).try_into_fraction().unwrap(), 1.0);
+        assert_eq!(real(150.0).try_into_fraction().unwrap(), 1.5);
+
+        assert_eq!(real(100.0).try_into_fraction_strict().unwrap(), 1.0);
+        assert!(matches!(
+            real(150.0).try_into_fraction_strict(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+        assert!(real(-5.0).try_into_fraction_strict().is_err());
+        assert!(real(f64::NAN).try_into_fraction_strict().is_err());
+
+        let null: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(null.try_into_fraction_opt().unwrap(), None);
+        assert_eq!(null.try_into_fraction_strict_opt().unwrap(), None);
+        assert!(null.try_into_fraction().is_err());
+    }
+
+    #[test]
+    fn test_bits() {
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::IntegerValue(0b101));
+        assert_eq!(field.try_into_bits().unwrap(), 0b101);
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::IntegerValue(-1));
+        assert_eq!(field.try_into_bits().unwrap(), 0xFFFF_FFFF);
+
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::Integer64Value(1 << 40));
+        assert_eq!(field.try_into_bits().unwrap(), 1 << 40);
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::RealValue(1.0));
+        assert!(field.try_into_bits().is_err());
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_bits_opt().unwrap(), None);
+    }
+
+    #[test]
+    fn test_coerce() {
+        let field = FieldResult::<GdalTraitError>::some;
+
+        assert_eq!(string_field(" 42 ").try_into_int_coerce().unwrap(), 42);
+        assert_eq!(
+            field(FieldValue::IntegerValue(7))
+                .try_into_int_coerce()
+                .unwrap(),
+            7
+        );
+        assert_eq!(
+            field(FieldValue::Integer64Value(7))
+                .try_into_int_coerce()
+                .unwrap(),
+            7
+        );
+        assert!(field(FieldValue::Integer64Value(i64::MAX))
+            .try_into_int_coerce()
+            .is_err());
+        assert!(field(FieldValue::RealValue(1.5))
+            .try_into_int_coerce()
+            .is_err());
+        assert!(string_field("1.5").try_into_int_coerce().is_err());
+
+        assert_eq!(
+            string_field("5000000000").try_into_int64_coerce().unwrap(),
+            5_000_000_000
+        );
+        assert_eq!(string_field("1.5").try_into_real_coerce().unwrap(), 1.5);
+        assert_eq!(
+            field(FieldValue::IntegerValue(2))
+                .try_into_real_coerce()
+                .unwrap(),
+            2.0
+        );
+        assert!(string_field("n/a").try_into_real_coerce().is_err());
+
+        assert_eq!(
+            field(FieldValue::RealValue(1.5))
+                .try_into_string_coerce()
+                .unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            field(FieldValue::Integer64Value(-3))
+                .try_into_string_coerce()
+                .unwrap(),
+            "-3"
+        );
+        assert!(field(FieldValue::IntegerListValue(vec![1]))
+            .try_into_string_coerce()
+            .is_err());
+
+        assert_eq!(string_field(" ").try_into_real_coerce_opt().unwrap(), None);
+        assert_eq!(
+            string_field("")
+                .try_into_string_coerce_opt()
+                .unwrap()
+                .as_deref(),
+            Some("")
+        );
+        let null = FieldResult::<GdalTraitError>::null();
+        assert_eq!(null.try_into_int_coerce_opt().unwrap(), None);
+        assert!(null.try_into_int_coerce().is_err());
+    }
+
+    // CSV columns are read as strings unless GDAL is told to detect their types.
+    #[test]
+    fn test_coerce_mixed_column() {
+        use gdal::vector::LayerAccess;
+        use gdal::Dataset;
+
+        let csv = "name,height\nTower,42\nMast,12.5\nShed,\nRuin,unknown\n";
+        gdal::vsi::create_mem_file("/vsimem/mixed.csv", csv.as_bytes().to_vec()).unwrap();
+
+        let ds = Dataset::open("/vsimem/mixed.csv").unwrap();
+        let mut layer = ds.layer(0).unwrap();
+        let idx = layer.defn().field_index("height").unwrap();
+        let heights: Vec<Result<Option<f64>, GdalTraitError>> = layer
+            .features()
+            .map(|feature| FieldResult::from(feature.field(idx)).try_into_real_coerce_opt())
+            .collect();
+
+        assert_eq!(heights[0], Ok(Some(42.0)));
+        assert_eq!(heights[1], Ok(Some(12.5)));
+        assert_eq!(heights[2], Ok(None));
+        assert!(matches!(
+            heights[3],
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+
+        drop(layer);
+        drop(ds);
+        gdal::vsi::unlink_mem_file("/vsimem/mixed.csv").unwrap();
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn test_flags() {
+        bitflags::bitflags! {
+            #[derive(Debug, PartialEq)]
+            struct Status: u8 {
+                const ACTIVE = 0b01;
+                const VERIFIED = 0b10;
+            }
+        }
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::IntegerValue(0b11));
+        assert_eq!(
+            field.try_into_flags::<Status>().unwrap(),
+            Status::ACTIVE | Status::VERIFIED
+        );
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::IntegerValue(0b100));
+        assert!(field.try_into_flags::<Status>().is_err());
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::IntegerValue(256));
+        assert!(field.try_into_flags::<Status>().is_err());
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_flags_opt::<Status>().unwrap(), None);
+    }
+}
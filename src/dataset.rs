@@ -0,0 +1,550 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::path::Path;
+use std::ptr;
+use std::str::FromStr;
+
+use gdal::errors::GdalError;
+use gdal::vector::Layer;
+use gdal::{Dataset, DatasetOptions};
+
+use crate::{FromFeature, GdalTraitError};
+
+/// Identifies a layer within a dataset, by name or index.
+///
+/// With the `serde` feature, this is (de)serialized as a plain string or integer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum DatasetLayer {
+    Name(String),
+    Index(usize),
+}
+
+impl DatasetLayer {
+    /// Get the identified layer from an open dataset.
+    pub fn resolve<'a>(&self, dataset: &'a Dataset) -> Result<Layer<'a>, GdalTraitError> {
+        Ok(match self {
+            DatasetLayer::Name(name) => dataset.layer_by_name(name)?,
+            DatasetLayer::Index(index) => dataset.layer(*index)?,
+        })
+    }
+}
+
+impl From<&str> for DatasetLayer {
+    /// Parse a layer identifier, e.g. from a config file or the command line.
+    ///
+    /// Integers such as `"3"` become `Index`, anything else `Name`. A leading `#` forces the
+    /// rest to be treated as a name, e.g. `"#3"` for a layer named "3".
+    fn from(value: &str) -> Self {
+        if let Some(name) = value.strip_prefix('#') {
+            return DatasetLayer::Name(name.to_string());
+        }
+
+        match value.parse() {
+            Ok(index) => DatasetLayer::Index(index),
+            Err(_) => DatasetLayer::Name(value.to_string()),
+        }
+    }
+}
+
+impl From<usize> for DatasetLayer {
+    fn from(value: usize) -> Self {
+        DatasetLayer::Index(value)
+    }
+}
+
+impl FromStr for DatasetLayer {
+    type Err = Infallible;
+
+    /// See [`DatasetLayer::from`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+/// A container of several layers of a dataset, e.g. a whole GeoPackage.
+///
+/// Usually derived, reading each `Vec<T: FromFeature>` field from the layer given by
+/// `#[gdal(layer = "name")]` or `#[gdal(layer_index = N)]`:
+///
+/// ```ignore
+/// #[derive(FromDataset)]
+/// struct Network {
+///     #[gdal(layer = "Net_VAG_Link")]
+///     links: Vec<Link>,
+///     #[gdal(layer_index = 1)]
+///     nodes: Vec<Node>,
+/// }
+///
+/// let network = Network::from_dataset("network.gpkg")?;
+/// ```
+///
+/// The error type is `GdalTraitError`, unless set with `#[gdal(error = MyError)]` on the struct.
+pub trait FromDataset<E>
+where
+    Self: Sized,
+    E: Error + From<GdalTraitError>,
+{
+    /// Read all layers from an open dataset.
+    fn from_open_dataset(dataset: &Dataset) -> Result<Self, E>;
+
+    /// Open a dataset and read all layers of it.
+    fn from_dataset(path: impl AsRef<Path>) -> Result<Self, E> {
+        Self::from_open_dataset(&open_dataset(path)?)
+    }
+
+    /// Like [`FromDataset::from_dataset`], with GDAL config options set during the load, see
+    /// [`ConfigGuard`].
+    fn from_dataset_with_config(
+        path: impl AsRef<Path>,
+        config: &[(&str, &str)],
+    ) -> Result<Self, E> {
+        let _guard = ConfigGuard::set(config)?;
+        Self::from_dataset(path)
+    }
+
+    /// Like [`FromDataset::from_dataset`], for a dataset requiring [`DatasetCredentials`].
+    fn from_dataset_with_credentials(
+        path: impl AsRef<Path>,
+        credentials: &DatasetCredentials,
+    ) -> Result<Self, E> {
+        let _guard = credentials.set_config()?;
+        Self::from_open_dataset(&credentials.open(path)?)
+    }
+}
+
+/// Credentials for opening protected datasets, e.g. on S3 or in PostGIS, see
+/// [`crate::FromFeature::from_dataset_with_credentials`].
+///
+/// Credentials are either set as thread-local config options during the load, see
+/// [`ConfigGuard`], or passed as open options to the driver, so they never end up in the global
+/// GDAL state. Supported backends:
+///
+/// - S3 (`/vsis3/`): `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`
+/// - Azure Blob Storage (`/vsiaz/`): `AZURE_STORAGE_ACCOUNT` and `AZURE_STORAGE_ACCESS_KEY`
+/// - HTTP(S) (`/vsicurl/`): `GDAL_HTTP_USERPWD` for basic authentication
+/// - PostgreSQL/PostGIS: the `USER` and `PASSWORD` open options
+///
+/// Anything else can be given with [`DatasetCredentials::with_config_option`] and
+/// [`DatasetCredentials::with_open_option`]. The `Debug` output hides all values.
+#[derive(Clone, Default)]
+pub struct DatasetCredentials {
+    config_options: Vec<(String, String)>,
+    open_options: Vec<(String, String)>,
+}
+
+impl DatasetCredentials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_s3(self, access_key_id: &str, secret_access_key: &str) -> Self {
+        self.with_config_option("AWS_ACCESS_KEY_ID", access_key_id)
+            .with_config_option("AWS_SECRET_ACCESS_KEY", secret_access_key)
+    }
+
+    /// A session token for temporary S3 credentials, in addition to [`DatasetCredentials::with_s3`].
+    pub fn with_s3_session_token(self, session_token: &str) -> Self {
+        self.with_config_option("AWS_SESSION_TOKEN", session_token)
+    }
+
+    pub fn with_azure(self, account: &str, access_key: &str) -> Self {
+        self.with_config_option("AZURE_STORAGE_ACCOUNT", account)
+            .with_config_option("AZURE_STORAGE_ACCESS_KEY", access_key)
+    }
+
+    pub fn with_http_basic(self, user: &str, password: &str) -> Self {
+        self.with_config_option("GDAL_HTTP_USERPWD", &format!("{user}:{password}"))
+    }
+
+    pub fn with_postgres(self, user: &str, password: &str) -> Self {
+        self.with_open_option("USER", user)
+            .with_open_option("PASSWORD", password)
+    }
+
+    /// Set a GDAL config option during the load.
+    pub fn with_config_option(mut self, key: &str, value: &str) -> Self {
+        self.config_options
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Pass an open option to the driver.
+    pub fn with_open_option(mut self, key: &str, value: &str) -> Self {
+        self.open_options.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the config options for the current thread, until the guard is dropped.
+    pub(crate) fn set_config(&self) -> Result<ConfigGuard, GdalTraitError> {
+        let options = self
+            .config_options
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        ConfigGuard::set(&options)
+    }
+
+    /// Open a dataset with the open options, see [`open_dataset`].
+    ///
+    /// The config options must already be set, see [`DatasetCredentials::set_config`].
+    pub(crate) fn open(&self, path: impl AsRef<Path>) -> Result<Dataset, GdalTraitError> {
+        let path = path.as_ref();
+        let open_options = self
+            .open_options
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>();
+        let open_options = open_options.iter().map(String::as_str).collect::<Vec<_>>();
+
+        Dataset::open_ex(
+            path,
+            DatasetOptions {
+                open_options: (!open_options.is_empty()).then_some(open_options.as_slice()),
+                ..Default::default()
+            },
+        )
+        .map_err(|source| GdalTraitError::OpenFailed {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+}
+
+impl fmt::Debug for DatasetCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = |options: &[(String, String)]| {
+            options
+                .iter()
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>()
+        };
+        f.debug_struct("DatasetCredentials")
+            .field("config_options", &keys(&self.config_options))
+            .field("open_options", &keys(&self.open_options))
+            .finish()
+    }
+}
+
+/// Sets GDAL config options for the current thread, e.g. `SHAPE_ENCODING=UTF-8`, restoring the
+/// previous values when dropped.
+///
+/// Thread-local options take precedence over global ones, so a load can be configured without
+/// touching the global GDAL state seen by other threads.
+///
+/// ```ignore
+/// let _guard = ConfigGuard::set(&[("OGR_SQLITE_SYNCHRONOUS", "OFF")])?;
+/// let links = Link::from_dataset("network.gpkg", &DatasetLayer::from("Net_VAG_Link"))?;
+/// ```
+#[must_use = "options are restored as soon as the guard is dropped"]
+pub struct ConfigGuard {
+    previous: Vec<(CString, Option<CString>)>,
+}
+
+impl ConfigGuard {
+    /// Set `options` as `(key, value)` pairs.
+    ///
+    /// Errors if a key or value contains a NUL byte, in which case nothing is changed.
+    pub fn set(options: &[(&str, &str)]) -> Result<Self, GdalTraitError> {
+        let options = options
+            .iter()
+            .map(|(key, value)| Ok((CString::new(*key)?, CString::new(*value)?)))
+            .collect::<Result<Vec<_>, std::ffi::NulError>>()
+            .map_err(GdalError::from)?;
+
+        let mut previous = Vec::with_capacity(options.len());
+        for (key, value) in options {
+            unsafe {
+                let current = gdal_sys::CPLGetThreadLocalConfigOption(key.as_ptr(), ptr::null());
+                let current = (!current.is_null()).then(|| CStr::from_ptr(current).to_owned());
+                gdal_sys::CPLSetThreadLocalConfigOption(key.as_ptr(), value.as_ptr());
+                previous.push((key, current));
+            }
+        }
+
+        Ok(Self { previous })
+    }
+}
+
+impl Drop for ConfigGuard {
+    /// Restores in reverse order, so that repeated keys end up with their original value.
+    fn drop(&mut self) {
+        for (key, value) in self.previous.iter().rev() {
+            let value = value.as_ref().map_or(ptr::null(), |value| value.as_ptr());
+            unsafe { gdal_sys::CPLSetThreadLocalConfigOption(key.as_ptr(), value) };
+        }
+    }
+}
+
+/// Read an entire layer of an open dataset, for `#[derive(FromDataset)]`.
+#[doc(hidden)]
+pub fn read_dataset_layer<T, const N: usize, E>(
+    dataset: &Dataset,
+    layer: &DatasetLayer,
+) -> Result<Vec<T>, E>
+where
+    T: FromFeature<N, E>,
+    E: Error + From<GdalTraitError>,
+{
+    T::from_layer(&mut layer.resolve(dataset)?)
+}
+
+/// GDAL path of a file within a zip archive, e.g. `/vsizip/countries.zip/countries.shp`.
+///
+/// GDAL reads the file directly from the archive, without extracting it.
+pub fn vsizip_path(archive: impl AsRef<Path>, inner_path: &str) -> String {
+    format!(
+        "/vsizip/{}/{}",
+        archive.as_ref().display(),
+        inner_path.trim_start_matches('/')
+    )
+}
+
+/// GDAL path of a remote file served over HTTP(S) or FTP, e.g.
+/// `/vsicurl/https://example.com/countries.gpkg`.
+///
+/// GDAL fetches the file with ranged requests, so only the parts being read are downloaded.
+pub fn vsicurl_path(url: &str) -> String {
+    format!("/vsicurl/{url}")
+}
+
+/// Open a dataset, e.g. for the `from_dataset` family of [`crate::FromFeature`].
+///
+/// Failures are reported as [`GdalTraitError::OpenFailed`], naming the path.
+pub(crate) fn open_dataset(path: impl AsRef<Path>) -> Result<Dataset, GdalTraitError> {
+    let path = path.as_ref();
+    Dataset::open(path).map_err(|source| GdalTraitError::OpenFailed {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use gdal::vector::{Geometry, LayerAccess};
+
+    use crate::FieldResult;
+
+    use super::*;
+
+    #[test]
+    fn test_vsi_paths() {
+        assert_eq!(
+            vsizip_path("fixtures/countries.zip", "/countries/countries.shp"),
+            "/vsizip/fixtures/countries.zip/countries/countries.shp"
+        );
+        assert_eq!(
+            vsicurl_path("https://example.com/countries.gpkg"),
+            "/vsicurl/https://example.com/countries.gpkg"
+        );
+    }
+
+    #[test]
+    fn test_parse_dataset_layer() {
+        assert_eq!(DatasetLayer::from("3"), DatasetLayer::Index(3));
+        assert_eq!(
+            DatasetLayer::from("roads"),
+            DatasetLayer::Name("roads".to_string())
+        );
+        assert_eq!(
+            DatasetLayer::from("#3"),
+            DatasetLayer::Name("3".to_string())
+        );
+        assert_eq!(
+            DatasetLayer::from("-1"),
+            DatasetLayer::Name("-1".to_string())
+        );
+        assert_eq!(
+            "roads".parse::<DatasetLayer>(),
+            Ok(DatasetLayer::Name("roads".to_string()))
+        );
+        assert_eq!(DatasetLayer::try_from("0"), Ok(DatasetLayer::Index(0)));
+    }
+
+    /// Only the FID of each feature.
+    struct Fid(Option<u64>);
+
+    impl FromFeature<0, GdalTraitError> for Fid {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = [];
+
+        fn read(
+            fid: Option<u64>,
+            _fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, GdalTraitError> {
+            Ok(Self(fid))
+        }
+    }
+
+    #[derive(crate::FromDataset)]
+    struct Network {
+        #[gdal(layer = "Net_VAG_Link")]
+        links: Vec<Fid>,
+        #[gdal(layer = "Net_VAG_Node")]
+        nodes: Vec<Fid>,
+    }
+
+    #[derive(crate::FromDataset)]
+    #[gdal(error = GdalTraitError)]
+    struct Countries {
+        #[gdal(layer_index = 0)]
+        countries: Vec<Fid>,
+    }
+
+    #[test]
+    fn test_from_dataset() {
+        let network = Network::from_dataset(
+            "fixtures/Gävle_test_2_Geopackage_530541/Gävle_test_2_530541.gpkg",
+        )
+        .unwrap();
+        assert_eq!(network.links.len(), 20224);
+        assert_eq!(network.nodes.len(), 13628);
+
+        let countries = Countries::from_dataset(
+            "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp",
+        )
+        .unwrap();
+        assert_eq!(countries.countries.len(), 177);
+        assert_eq!(countries.countries[0].0, Some(0));
+
+        assert!(matches!(
+            Network::from_dataset(
+                "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp"
+            ),
+            Err(GdalTraitError::GdalError(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let layers: Vec<DatasetLayer> = serde_json::from_str(r#"["roads", 3, "3"]"#).unwrap();
+        assert_eq!(
+            layers,
+            vec![
+                DatasetLayer::Name("roads".to_string()),
+                DatasetLayer::Index(3),
+                DatasetLayer::Name("3".to_string()),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&layers).unwrap(),
+            r#"["roads",3,"3"]"#
+        );
+    }
+
+    #[test]
+    fn test_open_failed() {
+        let Err(err) = open_dataset("fixtures/not_a_dataset.shp") else {
+            panic!("Expected the open to fail");
+        };
+        assert!(matches!(
+            &err,
+            GdalTraitError::OpenFailed { path, .. } if path == "fixtures/not_a_dataset.shp"
+        ));
+        assert!(err.to_string().contains("fixtures/not_a_dataset.shp"));
+
+        assert!(matches!(
+            Fid::from_zip("fixtures/missing.zip", "countries.shp", &DatasetLayer::Index(0)),
+            Err(GdalTraitError::OpenFailed { path, .. }) if path.starts_with("/vsizip/")
+        ));
+    }
+
+    fn config_option(key: &str) -> Option<String> {
+        let key = CString::new(key).unwrap();
+        unsafe {
+            let value = gdal_sys::CPLGetConfigOption(key.as_ptr(), ptr::null());
+            (!value.is_null()).then(|| CStr::from_ptr(value).to_string_lossy().into_owned())
+        }
+    }
+
+    #[test]
+    fn test_config_guard() {
+        {
+            let _outer = ConfigGuard::set(&[("GDAL_TRAITS_TEST", "outer")]).unwrap();
+            {
+                let _inner =
+                    ConfigGuard::set(&[("GDAL_TRAITS_TEST", "a"), ("GDAL_TRAITS_TEST", "b")])
+                        .unwrap();
+                assert_eq!(config_option("GDAL_TRAITS_TEST").as_deref(), Some("b"));
+            }
+            assert_eq!(config_option("GDAL_TRAITS_TEST").as_deref(), Some("outer"));
+        }
+        assert_eq!(config_option("GDAL_TRAITS_TEST"), None);
+
+        assert!(ConfigGuard::set(&[("GDAL_TRAITS_TEST", "a\0b")]).is_err());
+
+        let countries = Countries::from_dataset_with_config(
+            "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp",
+            &[("SHAPE_ENCODING", "UTF-8")],
+        )
+        .unwrap();
+        assert_eq!(countries.countries.len(), 177);
+        assert_eq!(config_option("SHAPE_ENCODING"), None);
+
+        let fids = Fid::from_dataset_with_config(
+            "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp",
+            &DatasetLayer::Index(0),
+            &[("SHAPE_ENCODING", "UTF-8")],
+        )
+        .unwrap();
+        assert_eq!(fids.len(), 177);
+    }
+
+    #[test]
+    fn test_dataset_credentials() {
+        let credentials = DatasetCredentials::new()
+            .with_s3("AKIDEXAMPLE", "secret")
+            .with_postgres("gis", "hunter2");
+        let debug = format!("{credentials:?}");
+        assert!(debug.contains("AWS_SECRET_ACCESS_KEY") && debug.contains("PASSWORD"));
+        assert!(!debug.contains("secret") && !debug.contains("hunter2"));
+
+        {
+            let _guard = credentials.set_config().unwrap();
+            assert_eq!(
+                config_option("AWS_ACCESS_KEY_ID").as_deref(),
+                Some("AKIDEXAMPLE")
+            );
+        }
+        assert_eq!(config_option("AWS_ACCESS_KEY_ID"), None);
+
+        let credentials = DatasetCredentials::new().with_http_basic("user", "password");
+        let countries = Countries::from_dataset_with_credentials(
+            "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp",
+            &credentials,
+        )
+        .unwrap();
+        assert_eq!(countries.countries.len(), 177);
+        assert_eq!(config_option("GDAL_HTTP_USERPWD"), None);
+
+        assert!(matches!(
+            Fid::from_dataset_with_credentials(
+                "fixtures/not_a_dataset.shp",
+                &DatasetLayer::Index(0),
+                &credentials,
+            ),
+            Err(GdalTraitError::OpenFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let ds = open_dataset(vsizip_path(
+            "fixtures/ne_110m_admin_0_countries.zip",
+            "ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp",
+        ))
+        .unwrap();
+
+        let by_index = DatasetLayer::Index(0).resolve(&ds).unwrap();
+        let by_name = DatasetLayer::Name("ne_110m_admin_0_countries".to_string())
+            .resolve(&ds)
+            .unwrap();
+        assert_eq!(by_index.name(), by_name.name());
+
+        assert!(DatasetLayer::Index(1).resolve(&ds).is_err());
+    }
+}
@@ -1,11 +1,37 @@
+use std::array;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::Infallible;
 use std::error::Error;
+use std::ffi::CStr;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use chrono::{DateTime, FixedOffset, NaiveDate};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use gdal::errors::GdalError;
-use gdal::spatial_ref::SpatialRef;
-use gdal::vector::{Feature, FieldValue, Geometry, Layer, LayerAccess};
-use paste::paste;
+use gdal::spatial_ref::{CoordTransform, SpatialRef};
+use gdal::vector::sql::Dialect;
+use gdal::vector::{
+    Feature, FeatureIterator, FieldValue, Geometry, Layer, LayerAccess, OGRFieldType,
+};
+use gdal::Dataset;
 
+use crate::dataset::{
+    open_dataset, vsicurl_path, vsizip_path, ConfigGuard, DatasetCredentials, DatasetLayer,
+};
+use crate::geometry::GeometryExt;
+use crate::intern::StringInterner;
+use crate::layer::{
+    layer_has_capability, layer_has_fast_count, layer_has_spatial_filter, FilterGuard,
+    IgnoredFields, LayerMeta, LoadedLayer,
+};
+use crate::options::ReadOptions;
+use crate::schema::{alias_field_index, check_fields, SchemaIssue};
+use crate::value::NullSentinel;
 use crate::GdalTraitError;
 
 /// Retrieval result of a field from a layer.
@@ -34,7 +60,82 @@ impl<E: Error + From<GdalError> + Clone> From<Result<Option<FieldValue>, GdalErr
     }
 }
 
+/// Compares values structurally, e.g. for deduplicating or memoizing on field results.
+///
+/// Reals compare by their bit patterns, like [`f64::total_cmp`], so that `FieldResult` can be
+/// [`Eq`] and [`Hash`]: NaN equals itself, while `0.0` and `-0.0` differ. Errors compare with
+/// the `PartialEq` of `E`.
+impl<E: Error + Clone + PartialEq> PartialEq for FieldResult<E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FieldResult::Some(a), FieldResult::Some(b)) => field_values_eq(a, b),
+            (FieldResult::Null, FieldResult::Null) => true,
+            (FieldResult::Error(a), FieldResult::Error(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<E: Error + Clone + Eq> Eq for FieldResult<E> {}
+
+/// Errors only hash their variant, so that `E` doesn't need to be [`Hash`].
+impl<E: Error + Clone> Hash for FieldResult<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        if let FieldResult::Some(value) = self {
+            hash_field_value(value, state);
+        }
+    }
+}
+
+fn field_values_eq(a: &FieldValue, b: &FieldValue) -> bool {
+    match (a, b) {
+        (FieldValue::RealValue(a), FieldValue::RealValue(b)) => a.to_bits() == b.to_bits(),
+        (FieldValue::RealListValue(a), FieldValue::RealListValue(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn hash_field_value<H: Hasher>(value: &FieldValue, state: &mut H) {
+    mem::discriminant(value).hash(state);
+    match value {
+        FieldValue::IntegerValue(v) => v.hash(state),
+        FieldValue::IntegerListValue(v) => v.hash(state),
+        FieldValue::Integer64Value(v) => v.hash(state),
+        FieldValue::Integer64ListValue(v) => v.hash(state),
+        FieldValue::StringValue(v) => v.hash(state),
+        FieldValue::StringListValue(v) => v.hash(state),
+        FieldValue::RealValue(v) => v.to_bits().hash(state),
+        FieldValue::RealListValue(v) => {
+            v.len().hash(state);
+            v.iter().for_each(|v| v.to_bits().hash(state));
+        }
+        FieldValue::DateValue(v) => v.hash(state),
+        FieldValue::DateTimeValue(v) => v.hash(state),
+    }
+}
+
 impl<E: Error + Clone> FieldResult<E> {
+    /// A successfully read field value.
+    ///
+    /// Together with [`FieldResult::null`] and [`FieldResult::error`], this allows building
+    /// fields by hand, e.g. to unit test a `read` implementation without a GDAL dataset.
+    pub fn some(value: FieldValue) -> Self {
+        FieldResult::Some(value)
+    }
+
+    /// A successfully read NULL field.
+    pub fn null() -> Self {
+        FieldResult::Null
+    }
+
+    /// A field which failed to be read.
+    pub fn error(e: E) -> Self {
+        FieldResult::Error(e)
+    }
+
     /// Convert the `FieldResult` into a nested `Result<Option<FieldValue>, GdalError> for
     /// convenient error/null handling.
     pub fn into_opt_res(self) -> Result<Option<FieldValue>, E> {
@@ -47,38 +148,71 @@ impl<E: Error + Clone> FieldResult<E> {
 }
 
 // For use within the `FieldResult` impl.
-// Consider replacing paste as it is unmaintained, but it seems solid enough.
+// Method names are passed explicitly to avoid depending on an identifier-pasting crate.
 macro_rules! try_into {
-    ($name:ident, $type:ty, $fval:ident) => {
-        paste! {
-            /// Attempt to convert a `FieldResult` into a desired type.
-            pub fn [<try_into_ $name>](&self) -> Result<$type, GdalTraitError> {
-                let FieldValue::$fval(rv) = self.to_owned().into_res()? else {
-                    return Err(
-                        GdalTraitError::InvalidFieldValue(
-                            format!("Failed to convert {self:?} into a {}", stringify!($ty))
-                        )
-                    );
-                };
-                Ok(rv)
-            }
+    ($name:ident, $name_opt:ident, $type:ty, $fval:ident) => {
+        /// Attempt to convert a `FieldResult` into a desired type.
+        pub fn $name(&self) -> Result<$type, GdalTraitError> {
+            let FieldValue::$fval(rv) = self.to_owned().into_res()? else {
+                return Err(GdalTraitError::InvalidFieldValue(format!(
+                    "Failed to convert {self:?} into a {}",
+                    stringify!($type)
+                )));
+            };
+            Ok(rv)
+        }
 
-            // Holy branching, batman!
-            /// Attempt to convert a `FieldResult` into an Option<...> of a desired type.
-            pub fn [<try_into_ $name _opt>](&self) -> Result<Option<$type>, GdalTraitError> {
-                Ok(match self.to_owned().into_opt_res()? {
-                    Some(val) => {
-                        let FieldValue::$fval(val) = val else {
-                            return Err(
-                                GdalTraitError::InvalidFieldValue(
-                                    format!("Failed to convert {self:?} into an Option<{}>", stringify!($ty))
-                                )
-                            );
-                        };
-                        Some(val)
-                    }
-                    None => None,
-                })
+        // Holy branching, batman!
+        /// Attempt to convert a `FieldResult` into an Option<...> of a desired type.
+        pub fn $name_opt(&self) -> Result<Option<$type>, GdalTraitError> {
+            Ok(match self.to_owned().into_opt_res()? {
+                Some(val) => {
+                    let FieldValue::$fval(val) = val else {
+                        return Err(GdalTraitError::InvalidFieldValue(format!(
+                            "Failed to convert {self:?} into an Option<{}>",
+                            stringify!($type)
+                        )));
+                    };
+                    Some(val)
+                }
+                None => None,
+            })
+        }
+    };
+}
+
+// For list fields where an empty list is semantically identical to NULL.
+macro_rules! try_into_nonempty {
+    ($name:ident, $name_opt:ident, $type:ty) => {
+        /// Attempt to convert a list `FieldResult` into an Option<...> of a desired type,
+        /// treating an empty list the same as NULL.
+        pub fn $name(&self) -> Result<Option<$type>, GdalTraitError> {
+            Ok(self.$name_opt()?.filter(|list| !list.is_empty()))
+        }
+    };
+}
+
+// For integer fields modelled as narrower types, e.g. GeoPackage `OFSTInt16` fields.
+macro_rules! try_into_narrow {
+    ($name:ident, $name_opt:ident, $type:ty) => {
+        /// Attempt to convert an integer `FieldResult` into a narrower integer type, erroring
+        /// on overflow.
+        pub fn $name(&self) -> Result<$type, GdalTraitError> {
+            let value = self.try_into_int()?;
+            <$type>::try_from(value).map_err(|_| {
+                GdalTraitError::InvalidFieldValue(format!(
+                    "{value} is out of range for a {}",
+                    stringify!($type)
+                ))
+            })
+        }
+
+        /// Attempt to convert an integer `FieldResult` into an Option<...> of a narrower integer
+        /// type, erroring on overflow.
+        pub fn $name_opt(&self) -> Result<Option<$type>, GdalTraitError> {
+            match self {
+                FieldResult::Null => Ok(None),
+                _ => self.$name().map(Some),
             }
         }
     };
@@ -97,16 +231,550 @@ impl FieldResult<GdalTraitError> {
         }
     }
 
-    try_into!(int, i32, IntegerValue);
-    try_into!(int_list, Vec<i32>, IntegerListValue);
-    try_into!(int64, i64, Integer64Value);
-    try_into!(int64_list, Vec<i64>, Integer64ListValue);
-    try_into!(string, String, StringValue);
-    try_into!(string_list, Vec<String>, StringListValue);
-    try_into!(real, f64, RealValue);
-    try_into!(real_list, Vec<f64>, RealListValue);
-    try_into!(date, NaiveDate, DateValue);
-    try_into!(date_time, DateTime<FixedOffset>, DateTimeValue);
+    /// Borrow the inner string of a `FieldResult` without cloning.
+    ///
+    /// Unlike [`FieldResult::try_into_string`], no allocation takes place, which is useful when
+    /// the value is only compared or inspected. `FieldResult::Null` will be treated as
+    /// GdalTraitError::NullField.
+    pub fn as_str(&self) -> Result<&str, GdalTraitError> {
+        match self {
+            FieldResult::Some(FieldValue::StringValue(s)) => Ok(s.as_str()),
+            FieldResult::Some(_) => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to borrow {self:?} as a &str"
+            ))),
+            FieldResult::Null => Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => Err(e.clone()),
+        }
+    }
+
+    /// Borrow the elements of a string list `FieldResult` exactly as GDAL provides them.
+    ///
+    /// No splitting, joining or trimming takes place, so elements containing commas or other
+    /// separators are kept intact. Unlike with [`FieldResult::as_str`], a plain string field
+    /// holding e.g. `"a,b"` is an error rather than being split.
+    pub fn try_into_string_list_raw(&self) -> Result<&[String], GdalTraitError> {
+        match self {
+            FieldResult::Some(FieldValue::StringListValue(list)) => Ok(list.as_slice()),
+            FieldResult::Some(_) => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to borrow {self:?} as a string list"
+            ))),
+            FieldResult::Null => Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => Err(e.clone()),
+        }
+    }
+
+    /// Attempt to convert a string `FieldResult` into a String, without trailing whitespace.
+    ///
+    /// Shapefile DBF string fields are fixed-width and often padded with trailing spaces,
+    /// e.g. `"SE  "`, which makes comparisons fail. Leading whitespace is kept.
+    pub fn try_into_string_trimmed(&self) -> Result<String, GdalTraitError> {
+        Ok(self.as_str()?.trim_end().to_string())
+    }
+
+    /// Attempt to convert a string `FieldResult` into an Option<String>, without trailing
+    /// whitespace.
+    pub fn try_into_string_trimmed_opt(&self) -> Result<Option<String>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_string_trimmed().map(Some),
+        }
+    }
+
+    /// Attempt to convert a string `FieldResult` into a non-empty String, for formats such as
+    /// shapefiles where an empty string often stands for a missing value.
+    ///
+    /// Errors on an empty string. Whitespace-only strings are kept as they are, see
+    /// [`FieldResult::try_into_string_trimmed`].
+    pub fn try_into_string_nonempty(&self) -> Result<String, GdalTraitError> {
+        match self.as_str()? {
+            "" => Err(GdalTraitError::InvalidFieldValue(
+                "Failed to convert an empty string into a non-empty String".to_string(),
+            )),
+            s => Ok(s.to_string()),
+        }
+    }
+
+    /// Attempt to convert a string `FieldResult` into an Option<String>, treating an empty
+    /// string the same as NULL.
+    pub fn try_into_string_nonempty_opt(&self) -> Result<Option<String>, GdalTraitError> {
+        Ok(self.try_into_string_opt()?.filter(|s| !s.is_empty()))
+    }
+
+    /// Attempt to convert a string `FieldResult` into a [`PathBuf`], e.g. a column of imagery
+    /// tile or attachment paths.
+    ///
+    /// The path is taken as is, neither resolved nor checked to exist. Errors on an empty
+    /// string, which is no path.
+    pub fn try_into_path(&self) -> Result<PathBuf, GdalTraitError> {
+        match self.as_str()? {
+            "" => Err(GdalTraitError::InvalidFieldValue(
+                "Failed to convert an empty string into a PathBuf".to_string(),
+            )),
+            s => Ok(PathBuf::from(s)),
+        }
+    }
+
+    /// Attempt to convert a string `FieldResult` into an Option<PathBuf>, treating an empty
+    /// string the same as NULL.
+    pub fn try_into_path_opt(&self) -> Result<Option<PathBuf>, GdalTraitError> {
+        Ok(self.try_into_string_nonempty_opt()?.map(PathBuf::from))
+    }
+
+    /// Attempt to convert a datetime `FieldResult` into a [`SystemTime`], e.g. for std-based
+    /// APIs, without requiring the `chrono` feature.
+    ///
+    /// Dates before the Unix epoch give a `SystemTime` before [`UNIX_EPOCH`]. Errors if the
+    /// instant can't be represented on this platform.
+    pub fn try_into_system_time(&self) -> Result<SystemTime, GdalTraitError> {
+        let dt = match self {
+            FieldResult::Some(FieldValue::DateTimeValue(dt)) => dt,
+            FieldResult::Some(_) => {
+                return Err(GdalTraitError::InvalidFieldValue(format!(
+                    "Failed to convert {self:?} into a SystemTime"
+                )))
+            }
+            FieldResult::Null => return Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => return Err(e.clone()),
+        };
+
+        // The nanoseconds always count forwards from the whole second.
+        let secs = dt.timestamp();
+        let nanos = Duration::from_nanos(dt.timestamp_subsec_nanos().into());
+        let time = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+        };
+
+        time.and_then(|time| time.checked_add(nanos))
+            .ok_or_else(|| {
+                GdalTraitError::InvalidFieldValue(format!("{dt} is out of range for a SystemTime"))
+            })
+    }
+
+    /// Attempt to convert a datetime `FieldResult` into an Option<SystemTime>.
+    pub fn try_into_system_time_opt(&self) -> Result<Option<SystemTime>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_system_time().map(Some),
+        }
+    }
+
+    /// Attempt to convert a numeric `FieldResult` into an f64, whether it's an integer, 64-bit
+    /// integer or real field.
+    ///
+    /// Smooths over drivers typing the same field differently, e.g. a population as integer
+    /// in one file and real in another. 64-bit integers beyond 2^53 lose precision.
+    pub fn try_into_number(&self) -> Result<f64, GdalTraitError> {
+        match self {
+            FieldResult::Some(FieldValue::IntegerValue(v)) => Ok(f64::from(*v)),
+            FieldResult::Some(FieldValue::Integer64Value(v)) => Ok(*v as f64),
+            FieldResult::Some(FieldValue::RealValue(v)) => Ok(*v),
+            FieldResult::Some(_) => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to convert {self:?} into a number"
+            ))),
+            FieldResult::Null => Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => Err(e.clone()),
+        }
+    }
+
+    /// Attempt to convert a numeric `FieldResult` into an Option<f64>.
+    pub fn try_into_number_opt(&self) -> Result<Option<f64>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_number().map(Some),
+        }
+    }
+
+    /// Attempt to convert a boolean `FieldResult` into a bool.
+    ///
+    /// GDAL stores booleans, e.g. GeoPackage `BOOLEAN` columns, as integer fields with the
+    /// `OFSTBoolean` subtype, so `0` and `1` are accepted and other integers rejected.
+    pub fn try_into_bool(&self) -> Result<bool, GdalTraitError> {
+        match self {
+            FieldResult::Some(FieldValue::IntegerValue(0)) => Ok(false),
+            FieldResult::Some(FieldValue::IntegerValue(1)) => Ok(true),
+            FieldResult::Some(_) => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to convert {self:?} into a bool"
+            ))),
+            FieldResult::Null => Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => Err(e.clone()),
+        }
+    }
+
+    /// Attempt to convert a boolean `FieldResult` into an Option<bool>.
+    pub fn try_into_bool_opt(&self) -> Result<Option<bool>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_bool().map(Some),
+        }
+    }
+
+    /// Attempt to convert an integer `FieldResult` into an i64.
+    ///
+    /// 32-bit integer fields are widened, as drivers differ in which integer columns they read
+    /// as 64-bit, e.g. GeoPackage `INTEGER` but not `MEDIUMINT` columns.
+    pub fn try_into_int64(&self) -> Result<i64, GdalTraitError> {
+        match self {
+            FieldResult::Some(FieldValue::Integer64Value(v)) => Ok(*v),
+            FieldResult::Some(FieldValue::IntegerValue(v)) => Ok(i64::from(*v)),
+            FieldResult::Some(_) => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to convert {self:?} into a i64"
+            ))),
+            FieldResult::Null => Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => Err(e.clone()),
+        }
+    }
+
+    /// Attempt to convert an integer `FieldResult` into an Option<i64>, widening 32-bit
+    /// integer fields.
+    pub fn try_into_int64_opt(&self) -> Result<Option<i64>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_int64().map(Some),
+        }
+    }
+
+    try_into!(try_into_int, try_into_int_opt, i32, IntegerValue);
+    try_into_narrow!(try_into_i16, try_into_i16_opt, i16);
+    try_into_narrow!(try_into_i8, try_into_i8_opt, i8);
+    try_into!(
+        try_into_int_list,
+        try_into_int_list_opt,
+        Vec<i32>,
+        IntegerListValue
+    );
+    try_into!(
+        try_into_int64_list,
+        try_into_int64_list_opt,
+        Vec<i64>,
+        Integer64ListValue
+    );
+    try_into!(try_into_string, try_into_string_opt, String, StringValue);
+    try_into!(
+        try_into_string_list,
+        try_into_string_list_opt,
+        Vec<String>,
+        StringListValue
+    );
+    try_into!(try_into_real, try_into_real_opt, f64, RealValue);
+    try_into!(
+        try_into_real_list,
+        try_into_real_list_opt,
+        Vec<f64>,
+        RealListValue
+    );
+
+    try_into_nonempty!(
+        try_into_int_list_nonempty_opt,
+        try_into_int_list_opt,
+        Vec<i32>
+    );
+    try_into_nonempty!(
+        try_into_int64_list_nonempty_opt,
+        try_into_int64_list_opt,
+        Vec<i64>
+    );
+    try_into_nonempty!(
+        try_into_string_list_nonempty_opt,
+        try_into_string_list_opt,
+        Vec<String>
+    );
+    try_into_nonempty!(
+        try_into_real_list_nonempty_opt,
+        try_into_real_list_opt,
+        Vec<f64>
+    );
+}
+
+// Date conversions require chrono, which may be too heavy for constrained targets.
+#[cfg(feature = "chrono")]
+impl FieldResult<GdalTraitError> {
+    try_into!(try_into_date, try_into_date_opt, NaiveDate, DateValue);
+    try_into!(
+        try_into_date_time,
+        try_into_date_time_opt,
+        DateTime<FixedOffset>,
+        DateTimeValue
+    );
+
+    /// Attempt to convert a `FieldResult` into a `DateTime<FixedOffset>`, parsing ISO-8601
+    /// strings as produced by [`read_field_iso8601`].
+    ///
+    /// Regular `DateTimeValue`s are passed through. Timestamps without a timezone are
+    /// interpreted as UTC.
+    pub fn try_into_date_time_iso(&self) -> Result<DateTime<FixedOffset>, GdalTraitError> {
+        match self.to_owned().into_res()? {
+            FieldValue::DateTimeValue(dt) => Ok(dt),
+            FieldValue::StringValue(s) => parse_iso8601(&s),
+            _ => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to convert {self:?} into a DateTime<FixedOffset>"
+            ))),
+        }
+    }
+
+    /// Attempt to convert a `FieldResult` into an Option<DateTime<FixedOffset>>, parsing
+    /// ISO-8601 strings as produced by [`read_field_iso8601`].
+    pub fn try_into_date_time_iso_opt(
+        &self,
+    ) -> Result<Option<DateTime<FixedOffset>>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_date_time_iso().map(Some),
+        }
+    }
+
+    /// Attempt to convert a datetime `FieldResult` into milliseconds since the Unix epoch.
+    ///
+    /// The stored offset is normalized to UTC, so equal instants in different timezones give
+    /// equal timestamps.
+    pub fn try_into_timestamp_millis(&self) -> Result<i64, GdalTraitError> {
+        Ok(self.try_into_date_time()?.timestamp_millis())
+    }
+
+    /// Attempt to convert a datetime `FieldResult` into an Option<...> of milliseconds since the
+    /// Unix epoch.
+    pub fn try_into_timestamp_millis_opt(&self) -> Result<Option<i64>, GdalTraitError> {
+        Ok(self
+            .try_into_date_time_opt()?
+            .map(|dt| dt.timestamp_millis()))
+    }
+
+    /// Attempt to convert a datetime `FieldResult` into a `DateTime<Utc>`.
+    ///
+    /// The instant is kept and the stored offset dropped, e.g. `12:00+02:00` becomes
+    /// `10:00Z`.
+    pub fn try_into_utc(&self) -> Result<DateTime<Utc>, GdalTraitError> {
+        Ok(self.try_into_date_time()?.with_timezone(&Utc))
+    }
+
+    /// Attempt to convert a datetime `FieldResult` into an Option<`DateTime<Utc>`>.
+    pub fn try_into_utc_opt(&self) -> Result<Option<DateTime<Utc>>, GdalTraitError> {
+        Ok(self
+            .try_into_date_time_opt()?
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl FieldResult<GdalTraitError> {
+    /// Attempt to convert a real list `FieldResult` into a [`SmallVec`](smallvec::SmallVec),
+    /// storing up to `K` elements inline to avoid a heap allocation per feature.
+    ///
+    /// Lists longer than `K` are still returned in full, spilling onto the heap.
+    pub fn try_into_real_list_small<const K: usize>(
+        &self,
+    ) -> Result<smallvec::SmallVec<[f64; K]>, GdalTraitError> {
+        match self {
+            FieldResult::Some(FieldValue::RealListValue(list)) => {
+                Ok(smallvec::SmallVec::from_slice(list))
+            }
+            FieldResult::Some(_) => Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to convert {self:?} into a SmallVec<[f64; {K}]>"
+            ))),
+            FieldResult::Null => Err(GdalTraitError::NullField),
+            FieldResult::Error(e) => Err(e.clone()),
+        }
+    }
+}
+
+/// Construct a date from separate integer year, month and day fields, as found in e.g. census
+/// and historical datasets.
+///
+/// Errors if any field is NULL or the date is invalid, e.g. February 30th.
+#[cfg(feature = "chrono")]
+pub fn date_from_fields(
+    year: &FieldResult<GdalTraitError>,
+    month: &FieldResult<GdalTraitError>,
+    day: &FieldResult<GdalTraitError>,
+) -> Result<NaiveDate, GdalTraitError> {
+    make_date(
+        year.try_into_int()?,
+        month.try_into_int()?,
+        day.try_into_int()?,
+    )
+}
+
+/// Like [`date_from_fields`], but for partial dates.
+///
+/// `None` if the year is NULL, while a NULL month or day is taken as the first, e.g. a year
+/// alone gives January 1st.
+#[cfg(feature = "chrono")]
+pub fn date_from_fields_opt(
+    year: &FieldResult<GdalTraitError>,
+    month: &FieldResult<GdalTraitError>,
+    day: &FieldResult<GdalTraitError>,
+) -> Result<Option<NaiveDate>, GdalTraitError> {
+    let Some(year) = year.try_into_int_opt()? else {
+        return Ok(None);
+    };
+
+    make_date(
+        year,
+        month.try_into_int_opt()?.unwrap_or(1),
+        day.try_into_int_opt()?.unwrap_or(1),
+    )
+    .map(Some)
+}
+
+#[cfg(feature = "chrono")]
+fn make_date(year: i32, month: i32, day: i32) -> Result<NaiveDate, GdalTraitError> {
+    u32::try_from(month)
+        .ok()
+        .zip(u32::try_from(day).ok())
+        .and_then(|(month, day)| NaiveDate::from_ymd_opt(year, month, day))
+        .ok_or_else(|| {
+            GdalTraitError::InvalidFieldValue(format!("Invalid date {year}-{month}-{day}"))
+        })
+}
+
+#[cfg(feature = "semver")]
+impl FieldResult<GdalTraitError> {
+    /// Attempt to parse a string `FieldResult` into a [`semver::Version`].
+    pub fn try_into_semver(&self) -> Result<semver::Version, GdalTraitError> {
+        let version = self.as_str()?;
+        semver::Version::parse(version).map_err(|e| {
+            GdalTraitError::InvalidFieldValue(format!(
+                "Failed to parse {version:?} as a semver version: {e}"
+            ))
+        })
+    }
+
+    /// Attempt to parse a string `FieldResult` into an Option<[`semver::Version`]>.
+    pub fn try_into_semver_opt(&self) -> Result<Option<semver::Version>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_semver().map(Some),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn parse_iso8601(s: &str) -> Result<DateTime<FixedOffset>, GdalTraitError> {
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f").map(|dt| {
+                FixedOffset::east_opt(0)
+                    .expect("UTC is a valid offset")
+                    .from_utc_datetime(&dt)
+            })
+        })
+        .map_err(|e| {
+            GdalTraitError::InvalidFieldValue(format!(
+                "Failed to parse {s:?} as an ISO-8601 datetime: {e}"
+            ))
+        })
+}
+
+/// Read a date/datetime field as an ISO-8601 string using GDAL's
+/// `OGR_F_GetFieldAsISO8601DateTime` (GDAL >= 3.7).
+///
+/// GDAL's own datetime-to-`FieldValue` conversion can differ between drivers in sub-second
+/// precision and timezone handling. The returned `FieldValue::StringValue` can be converted
+/// with [`FieldResult::try_into_date_time_iso`] for consistent results. Readers can have all
+/// their datetime fields read this way with [`FromFeature::ISO8601_DATE_TIMES`].
+#[cfg(gdal_ge_3_7)]
+pub fn read_field_iso8601(feature: &Feature, idx: usize) -> FieldResult<GdalTraitError> {
+    let c_feature = unsafe { feature.c_feature() };
+
+    let field_count = unsafe { gdal_sys::OGR_F_GetFieldCount(c_feature) };
+    let idx = match i32::try_from(idx) {
+        Ok(idx) if idx < field_count => idx,
+        _ => {
+            return FieldResult::Error(GdalTraitError::InvalidFieldValue(format!(
+                "Field index {idx} is out of range"
+            )))
+        }
+    };
+
+    unsafe {
+        if gdal_sys::OGR_F_IsFieldSetAndNotNull(c_feature, idx) == 0 {
+            return FieldResult::Null;
+        }
+
+        let ptr = gdal_sys::OGR_F_GetFieldAsISO8601DateTime(c_feature, idx, std::ptr::null_mut());
+        if ptr.is_null() {
+            return FieldResult::Error(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to read field {idx} as an ISO-8601 datetime"
+            )));
+        }
+
+        FieldResult::Some(FieldValue::StringValue(
+            CStr::from_ptr(ptr).to_string_lossy().into_owned(),
+        ))
+    }
+}
+
+/// The OGR style string of a feature, as used for per-feature styling by e.g. KML, DXF and
+/// MapInfo.
+///
+/// Returns `None` if the feature has no style.
+pub fn feature_style(feature: &Feature) -> Option<String> {
+    unsafe {
+        let ptr = gdal_sys::OGR_F_GetStyleString(feature.c_feature());
+        if ptr.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
+}
+
+/// The native data of a feature, i.e. its representation in the source format, e.g. for
+/// capturing properties which aren't modelled in [`FromFeature::FIELDS`] from
+/// [`FromFeature::read_with_feature`].
+///
+/// Only some drivers fill this in, and usually only when asked to with an open option: GeoJSON
+/// gives the whole feature object, including foreign members, with `NATIVE_DATA=YES`. Returns
+/// `None` otherwise.
+pub fn feature_native_data(feature: &Feature) -> Option<String> {
+    unsafe {
+        let ptr = gdal_sys::OGR_F_GetNativeData(feature.c_feature());
+        if ptr.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
+}
+
+/// Read the named fields of every feature of a layer, for when the fields are only known at
+/// runtime, e.g. in generic tools.
+///
+/// The runtime counterpart of [`FromFeature::from_layer`]: each inner vector holds the
+/// [`FieldResult`]s of one feature, in the order of `fields`. Missing fields give
+/// [`FieldResult::Error`]s rather than failing the read.
+pub fn read_dynamic(
+    layer: &mut Layer,
+    fields: &[&str],
+) -> Result<Vec<Vec<FieldResult<GdalTraitError>>>, GdalTraitError> {
+    let field_ids = layer_field_ids(layer, fields, &[], false, false);
+
+    Ok(layer
+        .features()
+        .map(|feature| {
+            (0..fields.len())
+                .map(|i| field_ids.read(&feature, i))
+                .collect()
+        })
+        .collect())
+}
+
+/// The FID passed to [`FromFeature::read`], erroring with [`GdalTraitError::NoFid`] if it's
+/// missing.
+pub fn require_fid(fid: Option<u64>) -> Result<u64, GdalTraitError> {
+    fid.ok_or(GdalTraitError::NoFid)
+}
+
+/// Like [`require_fid`], converted into a key type such as `i64` or a domain newtype.
+///
+/// Errors with [`GdalTraitError::InvalidFieldValue`] if the conversion fails.
+pub fn require_fid_as<T: TryFrom<u64>>(fid: Option<u64>) -> Result<T, GdalTraitError> {
+    let fid = require_fid(fid)?;
+    T::try_from(fid).map_err(|_| {
+        GdalTraitError::InvalidFieldValue(format!(
+            "Failed to convert FID {fid} into a {}",
+            std::any::type_name::<T>()
+        ))
+    })
 }
 
 pub trait FromFeature<const N: usize, E>
@@ -121,6 +789,42 @@ where
 
     const SRS: Option<SpatialRef> = None;
 
+    /// Expected OGR types of [`FromFeature::FIELDS`], used by [`FromFeature::check_schema`].
+    ///
+    /// If `None`, only the existence of the fields is checked.
+    const FIELD_TYPES: Option<[OGRFieldType::Type; N]> = None;
+
+    /// Values of [`FromFeature::FIELDS`] which stand for NULL, e.g. `-9999` in legacy data.
+    ///
+    /// Fields equal to their sentinel are read as [`FieldResult::Null`], so that the `_opt`
+    /// conversions work as expected.
+    const NULL_SENTINELS: [Option<NullSentinel>; N] = [None; N];
+
+    /// Whether [`FromFeature::FIELDS`] which aren't found by name are looked up by their alias,
+    /// see [`crate::layer_field_aliases`], e.g. for datasets where the display names are known
+    /// but not the physical column names. Also applies to [`FromFeature::check_schema`].
+    ///
+    /// Only drivers carrying aliases support this, e.g. FileGDB and GeoPackage, and GDAL 3.2 or
+    /// newer is required.
+    const MATCH_ALIASES: bool = false;
+
+    /// Whether datetime fields are read through GDAL's `OGR_F_GetFieldAsISO8601DateTime`
+    /// instead of its own conversion, which can lose sub-second precision or timezones depending
+    /// on the driver. The fields still convert with [`FieldResult::try_into_date_time`].
+    ///
+    /// Requires GDAL 3.7 or newer and the `chrono` feature, otherwise has no effect.
+    const ISO8601_DATE_TIMES: bool = false;
+
+    /// Whether the `from_layer` family sets the other fields of the layer as ignored for the
+    /// read, so that drivers supporting it, e.g. shapefiles and GeoPackages, skip them. This
+    /// speeds up reading a few fields of wide layers.
+    ///
+    /// Ignored fields read as NULL, so set this to `false` when
+    /// [`FromFeature::read_with_feature`] reads fields beyond [`FromFeature::FIELDS`], e.g.
+    /// through [`crate::dump_feature`]. Fields the layer ignored beforehand stay ignored either
+    /// way, and the earlier set is restored after the read.
+    const IGNORE_OTHER_FIELDS: bool = true;
+
     /// 'Read' fields, geometry, etc. from the source Feature.
     ///
     /// Called by [`FromFeature::from_feature`] and [`FromFeature::from_layer`].
@@ -130,115 +834,3021 @@ where
         geometry: Option<&Geometry>,
     ) -> Result<Self, E>;
 
+    /// 'Read' from the source [`Feature`] itself, for data beyond the fields and geometry, e.g.
+    /// [`feature_style`].
+    ///
+    /// Defaults to [`FromFeature::read`]. Called by all `from_*` methods in place of `read`.
+    /// Fields beyond [`FromFeature::FIELDS`] read as NULL unless
+    /// [`FromFeature::IGNORE_OTHER_FIELDS`] is `false`.
+    fn read_with_feature(
+        feature: &Feature,
+        fields: [FieldResult<GdalTraitError>; N],
+    ) -> Result<Self, E> {
+        Self::read(feature.fid(), fields, feature.geometry())
+    }
+
+    /// 'Read' with fields which are only resolved when requested, see [`LazyFields`].
+    ///
+    /// Called by [`FromFeature::from_layer_lazy`]. Defaults to reading all fields and calling
+    /// [`FromFeature::read_with_feature`]. Override for sparse, conditional reads where some
+    /// fields, e.g. large lists, are often not needed.
+    fn read_lazy(
+        _fid: Option<u64>,
+        fields: LazyFields<'_, N>,
+        _geometry: Option<&Geometry>,
+    ) -> Result<Self, E> {
+        Self::read_with_feature(fields.feature(), fields.read_all())
+    }
+
+    /// 'Read' with access to a [`StringInterner`] shared by all features, for deduplicating
+    /// repeated strings through [`FieldResult::try_into_interned`].
+    ///
+    /// Called by [`FromFeature::from_layer_interned`]. Defaults to
+    /// [`FromFeature::read_with_feature`], ignoring the interner.
+    fn read_interned(
+        feature: &Feature,
+        fields: [FieldResult<GdalTraitError>; N],
+        _interner: &mut StringInterner,
+    ) -> Result<Self, E> {
+        Self::read_with_feature(feature, fields)
+    }
+
     /// Convert a single [`Feature`].
     ///
     /// This might be needed in some situations, but sub-optimal performance-wise compared to
     /// [`FromFeature::from_layer`].
     fn from_feature(feature: Feature) -> Result<Self, E> {
-        let fields: [FieldResult<GdalTraitError>; N] = Self::FIELDS
-            .into_iter()
-            .map(|feat_name| feature.field_index(feat_name))
-            .map(|idx_res| match idx_res {
-                Ok(idx) => feature.field(idx).into(),
+        let c_defn = unsafe { gdal_sys::OGR_F_GetDefnRef(feature.c_feature()) };
+        let fields: [FieldResult<GdalTraitError>; N] = array::from_fn(|i| {
+            let fname = Self::FIELDS[i];
+            match resolve_field_index(
+                feature.field_index(fname),
+                c_defn,
+                fname,
+                Self::MATCH_ALIASES,
+            ) {
+                Ok(idx) => without_sentinel(
+                    read_field(&feature, idx, Self::ISO8601_DATE_TIMES),
+                    Self::NULL_SENTINELS[i].as_ref(),
+                ),
                 Err(e) => FieldResult::Error(e.into()),
-            })
-            .collect::<Vec<FieldResult<_>>>()
-            .try_into()
-            .unwrap();
+            }
+        });
 
-        Self::read(feature.fid(), fields, feature.geometry())
+        Self::read_with_feature(&feature, fields)
+    }
+
+    /// Verify that all [`FromFeature::FIELDS`] exist in the layer, with the types given by
+    /// [`FromFeature::FIELD_TYPES`] if any.
+    ///
+    /// Meant as a cheap check to fail early, before a long [`FromFeature::from_layer`].
+    fn check_schema(layer: &Layer) -> Result<(), Vec<SchemaIssue>> {
+        check_fields(
+            layer,
+            &Self::FIELDS,
+            Self::FIELD_TYPES.as_ref().map(|types| types.as_slice()),
+            Self::MATCH_ALIASES,
+        )
     }
 
     /// Reads an entire vector [`Layer`].
+    ///
+    /// Fields of the layer not in [`FromFeature::FIELDS`] are ignored for the read, see
+    /// [`FromFeature::IGNORE_OTHER_FIELDS`]. Filters set on the layer beforehand apply.
+    ///
+    /// The result is pre-allocated for layers with a fast feature count, see
+    /// [`layer_has_fast_count`].
     fn from_layer(layer: &mut Layer) -> Result<Vec<Self>, E> {
-        let field_ids: Vec<Result<usize, GdalError>> = Self::FIELDS
-            .into_iter()
-            .map(|fname| layer.defn().field_index(fname))
-            .collect();
+        Self::from_layer_with(layer, &ReadOptions::default())
+    }
+
+    /// Reads an entire vector [`Layer`] into any collection, e.g. a `HashSet<Self>`,
+    /// `BTreeSet<Self>` or `VecDeque<Self>`.
+    ///
+    /// Reading stops at the first error.
+    fn from_layer_collect<C: FromIterator<Self>>(layer: &mut Layer) -> Result<C, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
 
         layer
             .features()
             .map(|feature| {
-                let fields: [FieldResult<GdalTraitError>; N] = field_ids
-                    .iter()
-                    .map(|index| match index {
-                        Ok(index) => feature.field(*index).into(),
-                        Err(e) => FieldResult::Error((e.clone()).into()),
-                    })
-                    .collect::<Vec<FieldResult<_>>>()
-                    .try_into()
-                    .unwrap();
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
 
-                Self::read(feature.fid(), fields, feature.geometry())
+                Self::read_with_feature(&feature, fields)
             })
             .collect()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use gdal::vector::{Geometry, LayerAccess};
-    use gdal::Dataset;
-    use thiserror::Error;
 
-    use super::*;
+    /// Reads an entire vector [`Layer`] which is known to have no NULL values in
+    /// [`FromFeature::FIELDS`].
+    ///
+    /// A fast path for clean data: field indices are resolved up front, erroring immediately for
+    /// missing fields, and the first NULL field aborts the read with
+    /// [`GdalTraitError::NullField`] instead of being passed on as [`FieldResult::Null`]. `read`
+    /// thus only ever sees [`FieldResult::Some`]. Only use this when every field is required,
+    /// as optional fields which are NULL make the whole read fail.
+    fn from_layer_assume_present(layer: &mut Layer) -> Result<Vec<Self>, E> {
+        let layer_name = layer.name();
+        let c_defn = unsafe { gdal_sys::OGR_L_GetLayerDefn(layer.c_layer()) };
+        let indices = Self::FIELDS
+            .iter()
+            .map(|fname| {
+                resolve_field_index(
+                    layer.defn().field_index(fname),
+                    c_defn,
+                    fname,
+                    Self::MATCH_ALIASES,
+                )
+            })
+            .collect::<Result<Vec<usize>, GdalError>>()
+            .map_err(|e| GdalTraitError::from(e).with_context(Some(&layer_name), None))?;
+        let _ignored = Self::IGNORE_OTHER_FIELDS
+            .then(|| IgnoredFields::keep_only(layer, indices.iter().copied()))
+            .transpose()?;
 
-    #[derive(Debug, Error)]
+        layer
+            .features()
+            .map(|feature| {
+                // The array is filled in place, the remaining fields being skipped after the
+                // first failure.
+                let mut failure = None;
+                let fields: [FieldResult<GdalTraitError>; N] = array::from_fn(|i| {
+                    if failure.is_some() {
+                        return FieldResult::Null;
+                    }
+
+                    let sentinel = Self::NULL_SENTINELS[i];
+                    let value = match read_field(&feature, indices[i], Self::ISO8601_DATE_TIMES) {
+                        FieldResult::Some(value)
+                            if !sentinel.is_some_and(|s| s.matches(&value)) =>
+                        {
+                            Ok(value)
+                        }
+                        FieldResult::Error(e) => Err(e),
+                        _ => Err(GdalTraitError::NullField),
+                    };
+                    match value {
+                        Ok(value) => FieldResult::Some(value),
+                        Err(e) => {
+                            failure = Some(e.with_context(Some(&layer_name), feature.fid()));
+                            FieldResult::Null
+                        }
+                    }
+                });
+
+                if let Some(e) = failure {
+                    return Err(e.into());
+                }
+                Self::read_with_feature(&feature, fields)
+            })
+            .collect()
+    }
+
+    /// Reads a vector [`Layer`] with filtering and geometry processing, see [`ReadOptions`].
+    ///
+    /// A spatial or attribute filter given in `options` replaces the layer's filter of that
+    /// kind during the read. Afterwards, the previous spatial filter is restored, while the
+    /// attribute filter is cleared, as GDAL can't query it. Filters which `options` don't replace
+    /// are left as set on the layer and apply. `ReadOptions::default()` reads the same as
+    /// [`FromFeature::from_layer`].
+    fn from_layer_with(layer: &mut Layer, options: &ReadOptions) -> Result<Vec<Self>, E> {
+        let transform = match &options.target_srs {
+            Some(target_srs) => {
+                let source_srs = layer.spatial_ref().ok_or_else(|| {
+                    GdalTraitError::GeometryOperation(
+                        "Layer has no SRS to reproject from".to_string(),
+                    )
+                })?;
+                Some(CoordTransform::new(&source_srs, target_srs).map_err(GdalTraitError::from)?)
+            }
+            None => None,
+        };
+
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        let mut filters = FilterGuard::new(layer);
+        if let Some((min_x, min_y, max_x, max_y)) = options.bbox {
+            layer.set_spatial_filter_rect(min_x, min_y, max_x, max_y);
+            filters.spatial = true;
+        }
+        if let Some(filter) = &options.attribute_filter {
+            layer
+                .set_attribute_filter(filter)
+                .map_err(GdalTraitError::from)?;
+            filters.attribute = true;
+        }
+
+        let limit = options.limit.unwrap_or(usize::MAX);
+        let capacity = if layer_has_fast_count(layer) {
+            usize::try_from(layer.feature_count()).map_or(0, |count| count.min(limit))
+        } else {
+            0
+        };
+
+        let mut items = Vec::with_capacity(capacity);
+        for mut feature in layer.features() {
+            if items.len() == limit {
+                break;
+            }
+            if options.skip_null_geometry && feature.geometry().is_none() {
+                continue;
+            }
+
+            let mut fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+            if options.trim_strings {
+                for field in &mut fields {
+                    if let FieldResult::Some(FieldValue::StringValue(s)) = field {
+                        s.truncate(s.trim_end().len());
+                    }
+                }
+            }
+
+            if transform.is_some()
+                || options.simplify_tolerance.is_some()
+                || options.linearize.is_some()
+            {
+                if let Some(geometry) = feature.geometry() {
+                    let mut geometry = match options.linearize {
+                        Some(max_angle_step) => geometry.linearize(max_angle_step)?,
+                        None => geometry.clone(),
+                    };
+                    if let Some(transform) = &transform {
+                        geometry = geometry
+                            .transform(transform)
+                            .map_err(GdalTraitError::from)?;
+                    }
+                    if let Some(tolerance) = options.simplify_tolerance {
+                        geometry = geometry
+                            .simplify_preserve_topology(tolerance)
+                            .map_err(GdalTraitError::from)?;
+                    }
+                    feature
+                        .set_geometry(geometry)
+                        .map_err(GdalTraitError::from)?;
+                }
+            }
+
+            items.push(Self::read_with_feature(&feature, fields)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Reads the features of a vector [`Layer`] matching an OGR SQL `WHERE` clause, e.g.
+    /// `"ISO_A3 = 'SWE'"`.
+    ///
+    /// The clause is set as the layer's attribute filter and cleared afterwards. Drivers use any
+    /// attribute index for simple comparisons such as `field = value`, e.g. shapefile `.ind`
+    /// indices or GeoPackage/SQLite indices, instead of scanning every feature.
+    fn from_layer_where(layer: &mut Layer, where_clause: &str) -> Result<Vec<Self>, E> {
+        Self::from_layer_with(
+            layer,
+            &ReadOptions::default().with_attribute_filter(where_clause),
+        )
+    }
+
+    /// Reads the features of a vector [`Layer`] whose `field` equals `value`, see
+    /// [`FromFeature::from_layer_where`].
+    ///
+    /// The filter is quoted according to the type of `value`, which may be an integer, real,
+    /// string or date. Errors for other values, e.g. lists, and for NaN.
+    fn from_layer_by_attr(
+        layer: &mut Layer,
+        field: &str,
+        value: &FieldValue,
+    ) -> Result<Vec<Self>, E> {
+        Self::from_layer_where(layer, &equals_filter(field, value)?)
+    }
+
+    /// Reads an entire vector [`Layer`], along with its metadata.
+    fn from_layer_with_meta(layer: &mut Layer) -> Result<LoadedLayer<Self>, E> {
+        let meta = LayerMeta::from_layer(layer);
+        let items = Self::from_layer(layer)?;
+
+        Ok(LoadedLayer { items, meta })
+    }
+
+    /// Open a dataset and read an entire layer of it.
+    fn from_dataset(path: impl AsRef<Path>, layer: &DatasetLayer) -> Result<Vec<Self>, E> {
+        let dataset = open_dataset(path)?;
+        let mut layer = layer.resolve(&dataset)?;
+
+        Self::from_layer(&mut layer)
+    }
+
+    /// Open a dataset and read several layers of it in parallel, e.g. the tiles of a
+    /// GeoPackage, merging the features in the order of `layers`.
+    ///
+    /// GDAL datasets can't be shared between threads, so the dataset is opened by path once per
+    /// rayon work split, at most once per layer. This trades extra opens, e.g. network requests
+    /// for remote datasets, for reading layers concurrently, which pays off for large layers on
+    /// multicore machines.
+    /// Reading stops at the first error.
+    #[cfg(feature = "rayon")]
+    fn from_dataset_parallel(
+        path: impl AsRef<Path> + Sync,
+        layers: &[DatasetLayer],
+    ) -> Result<Vec<Self>, E>
+    where
+        Self: Send,
+        E: Send,
+    {
+        use rayon::prelude::*;
+
+        let loads = layers
+            .par_iter()
+            .map_init(
+                || open_dataset(&path),
+                |dataset, layer| {
+                    let dataset = dataset.as_ref().map_err(|e| e.clone())?;
+                    Self::from_layer(&mut layer.resolve(dataset)?)
+                },
+            )
+            .collect::<Result<Vec<Vec<Self>>, E>>()?;
+
+        Ok(loads.into_iter().flatten().collect())
+    }
+
+    /// Open a dataset and read an entire layer of it, along with the layer's metadata and the
+    /// dataset path as [`LayerMeta::source`].
+    fn from_dataset_with_meta(
+        path: impl AsRef<Path>,
+        layer: &DatasetLayer,
+    ) -> Result<LoadedLayer<Self>, E> {
+        let dataset = open_dataset(&path)?;
+        let mut layer = layer.resolve(&dataset)?;
+
+        let mut loaded = Self::from_layer_with_meta(&mut layer)?;
+        loaded.meta.source = Some(path.as_ref().display().to_string());
+        Ok(loaded)
+    }
+
+    /// Like [`FromFeature::from_dataset`], with GDAL config options set during the load, e.g.
+    /// `[("SHAPE_ENCODING", "UTF-8")]`, see [`ConfigGuard`].
+    fn from_dataset_with_config(
+        path: impl AsRef<Path>,
+        layer: &DatasetLayer,
+        config: &[(&str, &str)],
+    ) -> Result<Vec<Self>, E> {
+        let _guard = ConfigGuard::set(config)?;
+        Self::from_dataset(path, layer)
+    }
+
+    /// Like [`FromFeature::from_dataset`], for a dataset requiring credentials, e.g. on S3 or in
+    /// PostGIS.
+    ///
+    /// The credentials are only set for the open and read, see [`DatasetCredentials`].
+    fn from_dataset_with_credentials(
+        path: impl AsRef<Path>,
+        layer: &DatasetLayer,
+        credentials: &DatasetCredentials,
+    ) -> Result<Vec<Self>, E> {
+        let _guard = credentials.set_config()?;
+        let dataset = credentials.open(path)?;
+        let mut layer = layer.resolve(&dataset)?;
+
+        Self::from_layer(&mut layer)
+    }
+
+    /// Read an entire layer of a dataset within a zip archive, see [`vsizip_path`].
+    fn from_zip(
+        archive: impl AsRef<Path>,
+        inner_path: &str,
+        layer: &DatasetLayer,
+    ) -> Result<Vec<Self>, E> {
+        Self::from_dataset(vsizip_path(archive, inner_path), layer)
+    }
+
+    /// Read an entire layer of a remote dataset, see [`vsicurl_path`].
+    fn from_url(url: &str, layer: &DatasetLayer) -> Result<Vec<Self>, E> {
+        Self::from_dataset(vsicurl_path(url), layer)
+    }
+
+    /// Reads the features of a vector [`Layer`] whose geometries intersect `area`, e.g. for
+    /// clipping a load to a region.
+    ///
+    /// The layer's spatial filter is replaced by `area` as a prefilter, and restored afterwards,
+    /// followed by an exact [`GeometryExt::intersects`] test. `area` must be in the layer SRS. Features without
+    /// geometry are skipped.
+    fn from_layer_intersecting(layer: &mut Layer, area: &Geometry) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+        let mut filters = FilterGuard::new(layer);
+        layer.set_spatial_filter(area);
+        filters.spatial = true;
+
+        layer
+            .features()
+            .filter_map(|feature| {
+                let intersects = match feature.geometry() {
+                    Some(geometry) => GeometryExt::intersects(geometry, area),
+                    None => Ok(false),
+                };
+
+                match intersects {
+                    Ok(true) => {
+                        let fields: [FieldResult<GdalTraitError>; N] =
+                            read_fields(&feature, &field_ids);
+                        Some(Self::read_with_feature(&feature, fields))
+                    }
+                    Ok(false) => None,
+                    Err(e) => Some(Err(e.into())),
+                }
+            })
+            .collect()
+    }
+
+    /// Reads an entire vector [`Layer`] through a shared reference, by fetching FIDs
+    /// `0..feature_count()` instead of iterating.
+    ///
+    /// The layer's read cursor is left untouched, so no `&mut` is needed. Only suited for drivers
+    /// with zero-based, sequential FIDs and efficient random reads, e.g. shapefile and Memory.
+    /// Errors with [`GdalTraitError::FeatureNotFound`] for other FIDs, e.g. for GeoPackage which
+    /// numbers features from 1, and up front with [`GdalTraitError::UnsupportedLayer`] for
+    /// drivers without random reads.
+    ///
+    /// Fetching by FID ignores filters, so a layer with a spatial filter is an
+    /// [`GdalTraitError::UnsupportedLayer`] error too. GDAL can't tell whether an attribute
+    /// filter is set, so the layer mustn't have one, or the wrong features are read.
+    fn from_layer_random(layer: &Layer) -> Result<Vec<Self>, E> {
+        let unsupported = if !layer_has_capability(layer, "RandomRead") {
+            Some("no random reads")
+        } else if layer_has_spatial_filter(layer) {
+            Some("spatial filters don't apply to random reads")
+        } else {
+            None
+        };
+        if let Some(reason) = unsupported {
+            return Err(GdalTraitError::UnsupportedLayer(reason.to_string())
+                .with_context(Some(&layer.name()), None)
+                .into());
+        }
+
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        (0..layer.feature_count())
+            .map(|fid| {
+                let feature = layer.feature(fid).ok_or_else(|| {
+                    GdalTraitError::FeatureNotFound(fid).with_context(Some(&layer.name()), None)
+                })?;
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+
+                Self::read_with_feature(&feature, fields)
+            })
+            .collect()
+    }
+
+    /// Reads an entire vector [`Layer`] through [`FromFeature::read_interned`].
+    ///
+    /// The interner can be reused across layers, so e.g. categories shared between layers
+    /// are only allocated once.
+    fn from_layer_interned(
+        layer: &mut Layer,
+        interner: &mut StringInterner,
+    ) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        layer
+            .features()
+            .map(|feature| {
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+
+                Self::read_interned(&feature, fields, interner)
+            })
+            .collect()
+    }
+
+    /// Reads the features with the given FIDs, in order, skipping FIDs which don't exist.
+    ///
+    /// Features are fetched by FID instead of scanning the whole layer, e.g. for re-reading a
+    /// selection, with drivers supporting random reads, see [`LayerAccess::feature`]. Other
+    /// drivers emulate a fetch by reading from the start of the layer, so for them, FIDs given in
+    /// strictly ascending order are instead read in a single pass, stopping after the last one.
+    ///
+    /// Fetching by FID ignores the filters of the layer, so the spatial filter is ignored by the
+    /// single pass too, and restored afterwards. GDAL can't query the attribute filter, so it's
+    /// left in place, and only applies to the single pass.
+    fn from_fids(layer: &mut Layer, fids: &[u64]) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        let ascending = fids.windows(2).all(|pair| pair[0] < pair[1]);
+        if ascending && !layer_has_capability(layer, "RandomRead") {
+            let mut filters = FilterGuard::new(layer);
+            if layer_has_spatial_filter(layer) {
+                layer.clear_spatial_filter();
+                filters.spatial = true;
+            }
+
+            // Positions in `fids`, as the layer needn't be in FID order.
+            let mut items = Vec::with_capacity(fids.len());
+            for feature in layer.features() {
+                let Some(pos) = feature.fid().and_then(|fid| fids.binary_search(&fid).ok()) else {
+                    continue;
+                };
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+                items.push((pos, Self::read_with_feature(&feature, fields)?));
+
+                if items.len() == fids.len() {
+                    break;
+                }
+            }
+
+            items.sort_by_key(|(pos, _)| *pos);
+            return Ok(items.into_iter().map(|(_, item)| item).collect());
+        }
+
+        fids.iter()
+            .filter_map(|fid| layer.feature(*fid))
+            .map(|feature| {
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+
+                Self::read_with_feature(&feature, fields)
+            })
+            .collect()
+    }
+
+    /// Like [`FromFeature::from_fids`], but errors with [`GdalTraitError::FeatureNotFound`] for
+    /// FIDs which don't exist.
+    ///
+    /// Filters of the layer are ignored, but left in place.
+    fn from_fids_strict(layer: &mut Layer, fids: &[u64]) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        fids.iter()
+            .map(|fid| {
+                let feature = layer.feature(*fid).ok_or_else(|| {
+                    GdalTraitError::FeatureNotFound(*fid).with_context(Some(&layer.name()), None)
+                })?;
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+
+                Self::read_with_feature(&feature, fields)
+            })
+            .collect()
+    }
+
+    /// Reads an entire vector [`Layer`], sorted by the value of `field_name`.
+    ///
+    /// GDAL doesn't guarantee an iteration order, so this gives reproducible output, e.g. for
+    /// diffing or caching. All features are read, then sorted in memory by a stable sort, with
+    /// NULLs last and ties kept in iteration order. The sort field doesn't need to be part of
+    /// [`FromFeature::FIELDS`].
+    ///
+    /// [`FromFeature::from_dataset_sorted_by`] instead sorts in the driver, which is usually
+    /// more efficient for databases with an index on the field, e.g. GeoPackage or PostGIS.
+    fn from_layer_sorted_by(layer: &mut Layer, field_name: &str) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let key_id = layer
+            .defn()
+            .field_index(field_name)
+            .map_err(|e| GdalTraitError::from(e).with_context(Some(&field_ids.layer), None))?;
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[key_id])?;
+
+        let mut items = Vec::new();
+        for feature in layer.features() {
+            let key = feature.field(key_id).map_err(|e| {
+                GdalTraitError::from(e).with_context(Some(&field_ids.layer), feature.fid())
+            })?;
+
+            let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+            items.push((key, Self::read_with_feature(&feature, fields)?));
+        }
+
+        items.sort_by(|(a, _), (b, _)| compare_sort_keys(a, b));
+        Ok(items.into_iter().map(|(_, item)| item).collect())
+    }
+
+    /// Reads a layer of `dataset` through an OGR SQL `ORDER BY` on `field_name`.
+    ///
+    /// Sorting is left to the driver: SQL databases such as GeoPackage use their own engine and
+    /// indices, while other formats fall back on OGR SQL, which still sorts all features in
+    /// memory. Unlike with [`FromFeature::from_layer_sorted_by`], the position of NULLs and the
+    /// order of ties depend on the driver.
+    fn from_dataset_sorted_by(
+        dataset: &Dataset,
+        layer_name: &str,
+        field_name: &str,
+    ) -> Result<Vec<Self>, E> {
+        let query = format!(
+            "SELECT * FROM {} ORDER BY {}",
+            quote_identifier(layer_name),
+            quote_identifier(field_name)
+        );
+        let mut result = dataset
+            .execute_sql(&query, None, Dialect::DEFAULT)
+            .map_err(|e| GdalTraitError::from(e).with_context(Some(layer_name), None))?
+            .ok_or_else(|| {
+                GdalTraitError::InvalidFieldValue(format!("Query {query:?} returned no layer"))
+                    .with_context(Some(layer_name), None)
+            })?;
+
+        Self::from_layer(&mut result)
+    }
+
+    /// Reads an entire vector [`Layer`], keeping only the first feature for each unique
+    /// combination of values of `key_fields`.
+    ///
+    /// The key fields don't need to be part of [`FromFeature::FIELDS`]. Duplicates are dropped
+    /// before they are read, so no full intermediate vector is built.
+    fn from_layer_dedup_by(layer: &mut Layer, key_fields: &[&str]) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let key_ids = key_fields
+            .iter()
+            .map(|fname| layer.defn().field_index(fname))
+            .collect::<Result<Vec<usize>, GdalError>>()
+            .map_err(GdalTraitError::from)?;
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &key_ids)?;
+
+        // Read errors abort, so the keys can't hold errors, which makes them Eq.
+        let mut seen: HashSet<Vec<FieldResult<Infallible>>> = HashSet::new();
+        let mut items = Vec::new();
+        for feature in layer.features() {
+            let key = key_ids
+                .iter()
+                .map(|index| {
+                    feature.field(*index).map(|value| match value {
+                        Some(value) => FieldResult::Some(value),
+                        None => FieldResult::Null,
+                    })
+                })
+                .collect::<Result<Vec<FieldResult<Infallible>>, GdalError>>()
+                .map_err(GdalTraitError::from)?;
+
+            if !seen.insert(key) {
+                continue;
+            }
+
+            let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+            items.push(Self::read_with_feature(&feature, fields)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Reads an entire vector [`Layer`] through [`FromFeature::read_lazy`].
+    fn from_layer_lazy(layer: &mut Layer) -> Result<Vec<Self>, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        layer
+            .features()
+            .map(|feature| {
+                let fields = LazyFields {
+                    feature: &feature,
+                    field_ids: &field_ids,
+                };
+
+                Self::read_lazy(feature.fid(), fields, feature.geometry())
+            })
+            .collect()
+    }
+
+    /// Read every feature of a vector [`Layer`] and hand it to `f`, without collecting.
+    ///
+    /// The push-based counterpart of [`FromFeature::typed_features`], e.g. for streaming into a
+    /// database. Stops at the first failed read or callback error.
+    fn for_each_in_layer(
+        layer: &mut Layer,
+        mut f: impl FnMut(Self) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for item in Self::typed_features(layer) {
+            f(item?)?;
+        }
+        Ok(())
+    }
+
+    /// Lazily read a vector [`Layer`] as an iterator of `Result<Self, E>`.
+    ///
+    /// Unlike [`FromFeature::from_layer`], nothing is collected and a failed read does not stop
+    /// the iteration, so it composes with the standard iterator adapters, e.g.
+    /// `Country::typed_features(&mut layer).filter_map(Result::ok)`.
+    fn typed_features(layer: &mut Layer) -> TypedFeatures<'_, N, Self, E> {
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+
+        // Reading every field is merely slower, so failing to ignore fields isn't an error here.
+        let ignored = if Self::IGNORE_OTHER_FIELDS {
+            IgnoredFields::keep_only(layer, field_ids.indices()).ok()
+        } else {
+            None
+        };
+
+        TypedFeatures {
+            features: layer.features(),
+            field_ids,
+            _ignored: ignored,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Fields of a feature which are only read from GDAL when requested.
+///
+/// Positions correspond to those of [`FromFeature::FIELDS`].
+pub struct LazyFields<'a, const N: usize> {
+    feature: &'a Feature<'a>,
+    field_ids: &'a FieldIds,
+}
+
+impl<const N: usize> LazyFields<'_, N> {
+    /// Read the field at position `i`.
+    pub fn get(&self, i: usize) -> FieldResult<GdalTraitError> {
+        self.field_ids.read(self.feature, i)
+    }
+
+    /// Read all fields, as passed to [`FromFeature::read`].
+    pub fn read_all(&self) -> [FieldResult<GdalTraitError>; N] {
+        read_fields(self.feature, self.field_ids)
+    }
+
+    /// The source feature.
+    pub fn feature(&self) -> &Feature {
+        self.feature
+    }
+}
+
+/// The fields passed to [`FromFeature::read`], with lookups by name.
+///
+/// A convenience for wide schemas, where positional destructuring of the array is error-prone:
+///
+/// ```ignore
+/// let fields = Fields::new(&Self::FIELDS, fields);
+/// let pop_est = fields.by_name("POP_EST")?.try_into_real()?;
+/// ```
+pub struct Fields<const N: usize> {
+    names: &'static [&'static str; N],
+    fields: [FieldResult<GdalTraitError>; N],
+}
+
+impl<const N: usize> Fields<N> {
+    /// Wrap `fields`, where `names` are the corresponding [`FromFeature::FIELDS`].
+    pub fn new(
+        names: &'static [&'static str; N],
+        fields: [FieldResult<GdalTraitError>; N],
+    ) -> Self {
+        Self { names, fields }
+    }
+
+    /// The field named `name`.
+    ///
+    /// Errors if `name` isn't one of the field names, e.g. due to a typo.
+    pub fn by_name(&self, name: &str) -> Result<&FieldResult<GdalTraitError>, GdalTraitError> {
+        self.names
+            .iter()
+            .position(|field_name| *field_name == name)
+            .map(|i| &self.fields[i])
+            .ok_or_else(|| GdalTraitError::InvalidFieldValue(format!("No field named {name:?}")))
+    }
+
+    /// The field at position `i`.
+    pub fn get(&self, i: usize) -> Option<&FieldResult<GdalTraitError>> {
+        self.fields.get(i)
+    }
+
+    /// Unwrap into the field array.
+    pub fn into_inner(self) -> [FieldResult<GdalTraitError>; N] {
+        self.fields
+    }
+}
+
+/// Iterator over the features of a layer, read into `T`.
+///
+/// Created by [`FromFeature::typed_features`].
+pub struct TypedFeatures<'a, const N: usize, T, E> {
+    features: FeatureIterator<'a>,
+    field_ids: FieldIds,
+    _ignored: Option<IgnoredFields>,
+    _marker: PhantomData<fn() -> Result<T, E>>,
+}
+
+impl<const N: usize, T, E> Iterator for TypedFeatures<'_, N, T, E>
+where
+    T: FromFeature<N, E>,
+    E: Error + From<GdalTraitError>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let feature = self.features.next()?;
+        let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &self.field_ids);
+
+        Some(T::read_with_feature(&feature, fields))
+    }
+}
+
+/// Like [`FromFeature`], but with a context which is built once per layer and passed to every
+/// read.
+///
+/// Useful when `read` needs expensive setup which is constant across features, e.g. compiling a
+/// regex, building a lookup table or reading the layer SRS.
+///
+/// Fields are resolved as by [`FromFeature`], with the same options. There is no counterpart to
+/// [`FromFeature::read_with_feature`], so other fields are best read in
+/// [`FromFeatureContext::make_context`].
+pub trait FromFeatureContext<const N: usize, E>
+where
+    Self: Sized,
+    E: Error + From<GdalTraitError>,
+{
+    const NUM_FIELDS: usize = N;
+
+    /// Desired fields from the input layer.
+    const FIELDS: [&'static str; N];
+
+    /// See [`FromFeature::NULL_SENTINELS`].
+    const NULL_SENTINELS: [Option<NullSentinel>; N] = [None; N];
+
+    /// See [`FromFeature::MATCH_ALIASES`].
+    const MATCH_ALIASES: bool = false;
+
+    /// See [`FromFeature::ISO8601_DATE_TIMES`].
+    const ISO8601_DATE_TIMES: bool = false;
+
+    /// See [`FromFeature::IGNORE_OTHER_FIELDS`]. Applies after
+    /// [`FromFeatureContext::make_context`], which sees all fields.
+    const IGNORE_OTHER_FIELDS: bool = true;
+
+    /// State shared between all reads of a layer.
+    type Context;
+
+    /// Build the [`FromFeatureContext::Context`] for a layer.
+    ///
+    /// Called once by [`FromFeatureContext::from_layer_ctx`], before any features are read.
+    fn make_context(layer: &Layer) -> Result<Self::Context, E>;
+
+    /// 'Read' fields, geometry, etc. from the source Feature, with access to the layer context.
+    fn read_ctx(
+        ctx: &Self::Context,
+        fid: Option<u64>,
+        fields: [FieldResult<GdalTraitError>; N],
+        geometry: Option<&Geometry>,
+    ) -> Result<Self, E>;
+
+    /// Reads an entire vector [`Layer`], building the context once.
+    fn from_layer_ctx(layer: &mut Layer) -> Result<Vec<Self>, E> {
+        let ctx = Self::make_context(layer)?;
+        let field_ids = layer_field_ids(
+            layer,
+            &Self::FIELDS,
+            &Self::NULL_SENTINELS,
+            Self::MATCH_ALIASES,
+            Self::ISO8601_DATE_TIMES,
+        );
+        let _ignored = field_ids.ignore_others(layer, Self::IGNORE_OTHER_FIELDS, &[])?;
+
+        layer
+            .features()
+            .map(|feature| {
+                let fields: [FieldResult<GdalTraitError>; N] = read_fields(&feature, &field_ids);
+
+                Self::read_ctx(&ctx, feature.fid(), fields, feature.geometry())
+            })
+            .collect()
+    }
+}
+
+/// Indices of the desired fields, resolved once per layer.
+pub(crate) struct FieldIds {
+    layer: String,
+    ids: Vec<Result<usize, GdalError>>,
+    null_sentinels: Vec<Option<NullSentinel>>,
+    iso8601: bool,
+}
+
+impl FieldIds {
+    /// Indices of the fields which were found.
+    pub(crate) fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ids.iter().filter_map(|id| id.as_ref().ok().copied())
+    }
+
+    /// If `enabled`, ignore all other fields of `layer` except `extra`, e.g. a sort key, until
+    /// the guard is dropped, see [`FromFeature::IGNORE_OTHER_FIELDS`].
+    pub(crate) fn ignore_others(
+        &self,
+        layer: &Layer,
+        enabled: bool,
+        extra: &[usize],
+    ) -> Result<Option<IgnoredFields>, GdalTraitError> {
+        enabled
+            .then(|| IgnoredFields::keep_only(layer, self.indices().chain(extra.iter().copied())))
+            .transpose()
+    }
+
+    /// Read the field at position `i` from a feature.
+    ///
+    /// Errors carry the layer name and FID as context.
+    pub(crate) fn read(&self, feature: &Feature, i: usize) -> FieldResult<GdalTraitError> {
+        let field: FieldResult<GdalTraitError> = match self.ids.get(i) {
+            Some(Ok(index)) => read_field(feature, *index, self.iso8601),
+            Some(Err(e)) => FieldResult::Error((e.clone()).into()),
+            None => FieldResult::Error(GdalTraitError::InvalidFieldValue(format!(
+                "Field position {i} is out of range for {} fields",
+                self.ids.len()
+            ))),
+        };
+
+        match field {
+            FieldResult::Error(e) => {
+                FieldResult::Error(e.with_context(Some(&self.layer), feature.fid()))
+            }
+            field => without_sentinel(field, self.null_sentinels.get(i).and_then(Option::as_ref)),
+        }
+    }
+}
+
+/// Read the field at `idx` from a feature, datetimes through `OGR_F_GetFieldAsISO8601DateTime`
+/// if `iso8601`, see [`FromFeature::ISO8601_DATE_TIMES`].
+fn read_field(feature: &Feature, idx: usize, iso8601: bool) -> FieldResult<GdalTraitError> {
+    #[cfg(all(gdal_ge_3_7, feature = "chrono"))]
+    if iso8601 && is_date_time_field(feature, idx) {
+        return match read_field_iso8601(feature, idx) {
+            FieldResult::Some(FieldValue::StringValue(s)) => match parse_iso8601(&s) {
+                Ok(dt) => FieldResult::Some(FieldValue::DateTimeValue(dt)),
+                Err(e) => FieldResult::Error(e),
+            },
+            field => field,
+        };
+    }
+    #[cfg(not(all(gdal_ge_3_7, feature = "chrono")))]
+    let _ = iso8601;
+
+    feature.field(idx).into()
+}
+
+#[cfg(all(gdal_ge_3_7, feature = "chrono"))]
+fn is_date_time_field(feature: &Feature, idx: usize) -> bool {
+    let Ok(idx) = i32::try_from(idx) else {
+        return false;
+    };
+    unsafe {
+        let c_field = gdal_sys::OGR_F_GetFieldDefnRef(feature.c_feature(), idx);
+        !c_field.is_null() && gdal_sys::OGR_Fld_GetType(c_field) == OGRFieldType::OFTDateTime
+    }
+}
+
+/// Turn `field` into [`FieldResult::Null`] if it's equal to `sentinel`.
+fn without_sentinel(
+    field: FieldResult<GdalTraitError>,
+    sentinel: Option<&NullSentinel>,
+) -> FieldResult<GdalTraitError> {
+    match (&field, sentinel) {
+        (FieldResult::Some(value), Some(sentinel)) if sentinel.matches(value) => FieldResult::Null,
+        _ => field,
+    }
+}
+
+/// Order of two sort keys, with NULLs last.
+///
+/// Numbers compare across integer and real fields. Values of unrelated types, or NaN, compare
+/// equal, which keeps them in iteration order.
+pub(crate) fn compare_sort_keys(a: &Option<FieldValue>, b: &Option<FieldValue>) -> Ordering {
+    let (a, b) = match (a, b) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a), Some(b)) => (a, b),
+    };
+
+    match (a, b) {
+        (FieldValue::Integer64Value(a), FieldValue::Integer64Value(b)) => a.cmp(b),
+        (FieldValue::StringValue(a), FieldValue::StringValue(b)) => a.cmp(b),
+        (FieldValue::DateValue(a), FieldValue::DateValue(b)) => a.cmp(b),
+        (FieldValue::DateTimeValue(a), FieldValue::DateTimeValue(b)) => a.cmp(b),
+        (a, b) => match (sort_number(a), sort_number(b)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            _ => Ordering::Equal,
+        },
+    }
+}
+
+fn sort_number(value: &FieldValue) -> Option<f64> {
+    match value {
+        FieldValue::IntegerValue(v) => Some(f64::from(*v)),
+        FieldValue::Integer64Value(v) => Some(*v as f64),
+        FieldValue::RealValue(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Quote an identifier for OGR SQL, e.g. a layer name with spaces.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// An OGR SQL `WHERE` clause for `field = value`.
+fn equals_filter(field: &str, value: &FieldValue) -> Result<String, GdalTraitError> {
+    let value = match value {
+        FieldValue::IntegerValue(v) => v.to_string(),
+        FieldValue::Integer64Value(v) => v.to_string(),
+        FieldValue::RealValue(v) if v.is_finite() => v.to_string(),
+        FieldValue::StringValue(v) => format!("'{}'", v.replace('\'', "''")),
+        FieldValue::DateValue(v) => format!("'{}'", v.format("%Y/%m/%d")),
+        value => {
+            return Err(GdalTraitError::InvalidFieldValue(format!(
+                "Failed to use {value:?} in an attribute filter"
+            )))
+        }
+    };
+
+    Ok(format!("{} = {value}", quote_identifier(field)))
+}
+
+/// Resolve the indices of the named fields in a layer, along with their NULL sentinels if any.
+///
+/// With `match_aliases`, fields not found by name are looked up by alias, see
+/// [`FromFeature::MATCH_ALIASES`]. With `iso8601`, datetimes are read as by
+/// [`FromFeature::ISO8601_DATE_TIMES`].
+pub(crate) fn layer_field_ids(
+    layer: &Layer,
+    fields: &[&str],
+    null_sentinels: &[Option<NullSentinel>],
+    match_aliases: bool,
+    iso8601: bool,
+) -> FieldIds {
+    let c_defn = unsafe { gdal_sys::OGR_L_GetLayerDefn(layer.c_layer()) };
+
+    FieldIds {
+        layer: layer.name(),
+        ids: fields
+            .iter()
+            .map(|fname| {
+                resolve_field_index(
+                    layer.defn().field_index(fname),
+                    c_defn,
+                    fname,
+                    match_aliases,
+                )
+            })
+            .collect(),
+        null_sentinels: null_sentinels.to_vec(),
+        iso8601,
+    }
+}
+
+/// Fall back on the field with alias `fname` if looking it up by name failed and
+/// `match_aliases`, keeping the original error if there's none.
+fn resolve_field_index(
+    by_name: Result<usize, GdalError>,
+    c_defn: gdal_sys::OGRFeatureDefnH,
+    fname: &str,
+    match_aliases: bool,
+) -> Result<usize, GdalError> {
+    by_name.or_else(|e| {
+        match_aliases
+            .then(|| unsafe { alias_field_index(c_defn, fname) })
+            .flatten()
+            .ok_or(e)
+    })
+}
+
+/// Read the fields at pre-resolved indices from a feature.
+///
+/// The array is filled in place, without an intermediate allocation. Positions beyond the
+/// resolved fields are errors, see [`FieldIds::read`].
+pub(crate) fn read_fields<const N: usize>(
+    feature: &Feature,
+    field_ids: &FieldIds,
+) -> [FieldResult<GdalTraitError>; N] {
+    array::from_fn(|i| field_ids.read(feature, i))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+
+    use gdal::vector::{Geometry, LayerAccess};
+    use gdal::Dataset;
+    use thiserror::Error;
+
+    use super::*;
+
+    #[derive(Debug, Error)]
     enum TestError {
         #[error("GDAL Trait Error: {0:?}")]
         GdalTraitError(#[from] GdalTraitError),
 
-        #[error("GDAL Error: {0:?}")]
-        GdalError(#[from] GdalError),
+        #[error("GDAL Error: {0:?}")]
+        GdalError(#[from] GdalError),
+
+        #[error("No Geomety")]
+        NoGeometry,
+
+        #[error("geo_type Error: {0}")]
+        GeoError(#[from] geo_types::Error),
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Country {
+        name: String,
+        iso_a2: Option<String>,
+        iso_a3: String,
+
+        pop_est: f64,
+        pop_year: i32,
+
+        geom: geo_types::Geometry<f64>,
+    }
+
+    impl FromFeature<5, TestError> for Country {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] =
+            ["NAME", "ISO_A2", "ISO_A3", "POP_EST", "POP_YEAR"];
+
+        const FIELD_TYPES: Option<[OGRFieldType::Type; Self::NUM_FIELDS]> = Some([
+            OGRFieldType::OFTString,
+            OGRFieldType::OFTString,
+            OGRFieldType::OFTString,
+            OGRFieldType::OFTReal,
+            OGRFieldType::OFTInteger,
+        ]);
+
+        fn read(
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            let [name_field, a2_field, a3_field, pop_est_field, pop_year_field] = fields;
+
+            let name = name_field.try_into_string()?;
+            let iso_a2 = a2_field.try_into_string_opt()?;
+            let iso_a3 = a3_field.try_into_string()?;
+
+            let pop_est = pop_est_field.try_into_real()?;
+            let pop_year = pop_year_field.try_into_int()?;
+
+            let geom = geometry.ok_or(TestError::NoGeometry)?.to_geo()?;
+
+            Ok(Self {
+                name,
+                iso_a2,
+                iso_a3,
+                pop_est,
+                pop_year,
+                geom,
+            })
+        }
+    }
+
+    #[test]
+    fn test_read_without_dataset() {
+        let fields = [
+            FieldResult::some(FieldValue::StringValue("Sweden".to_string())),
+            FieldResult::null(),
+            FieldResult::some(FieldValue::StringValue("SWE".to_string())),
+            FieldResult::some(FieldValue::RealValue(10285453.0)),
+            FieldResult::some(FieldValue::IntegerValue(2019)),
+        ];
+        let geometry = Geometry::from_wkt("POINT (18 59)").unwrap();
+
+        let country = Country::read(Some(110), fields, Some(&geometry)).unwrap();
+        assert_eq!(country.name, "Sweden");
+        assert_eq!(country.iso_a2, None);
+        assert_eq!(country.pop_year, 2019);
+
+        let fields = [
+            FieldResult::error(GdalTraitError::NullField),
+            FieldResult::null(),
+            FieldResult::null(),
+            FieldResult::null(),
+            FieldResult::null(),
+        ];
+        assert!(Country::read(None, fields, Some(&geometry)).is_err());
+    }
+
+    #[test]
+    fn test_field_result_eq_hash() {
+        fn hash(field: &FieldResult<GdalTraitError>) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            field.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a: FieldResult<GdalTraitError> = FieldResult::some(FieldValue::RealValue(1.5));
+        assert_eq!(a, FieldResult::some(FieldValue::RealValue(1.5)));
+        assert_eq!(
+            hash(&a),
+            hash(&FieldResult::some(FieldValue::RealValue(1.5)))
+        );
+        assert_ne!(a, FieldResult::some(FieldValue::IntegerValue(1)));
+        assert_ne!(a, FieldResult::null());
+
+        let nan: FieldResult<GdalTraitError> = FieldResult::some(FieldValue::RealValue(f64::NAN));
+        assert_eq!(nan, nan.clone());
+        assert_ne!(
+            FieldResult::<GdalTraitError>::some(FieldValue::RealValue(0.0)),
+            FieldResult::some(FieldValue::RealValue(-0.0))
+        );
+
+        let null: FieldResult<GdalTraitError> = FieldResult::null();
+        assert_eq!(null, FieldResult::null());
+        assert_eq!(
+            FieldResult::<GdalTraitError>::error(GdalTraitError::NullField),
+            FieldResult::error(GdalTraitError::NullField)
+        );
+
+        let mut seen = HashSet::new();
+        assert!(seen.insert(hash(&a)));
+        assert!(!seen.insert(hash(&a.clone())));
+    }
+
+    #[test]
+    fn test_as_str() {
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("SE".to_string()));
+        assert_eq!(field.as_str().unwrap(), "SE");
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::IntegerValue(1));
+        assert!(matches!(
+            field.as_str(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert!(matches!(field.as_str(), Err(GdalTraitError::NullField)));
+    }
+
+    #[test]
+    fn test_string_nonempty() {
+        let string =
+            |s: &str| FieldResult::<GdalTraitError>::some(FieldValue::StringValue(s.into()));
+
+        assert_eq!(string("SE").try_into_string_nonempty().unwrap(), "SE");
+        assert!(matches!(
+            string("").try_into_string_nonempty(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+        assert_eq!(string("  ").try_into_string_nonempty().unwrap(), "  ");
+
+        assert_eq!(string("").try_into_string_nonempty_opt().unwrap(), None);
+        assert_eq!(
+            string("  ").try_into_string_nonempty_opt().unwrap(),
+            Some("  ".to_string())
+        );
+        assert_eq!(
+            FieldResult::<GdalTraitError>::null()
+                .try_into_string_nonempty_opt()
+                .unwrap(),
+            None
+        );
+        assert!(FieldResult::<GdalTraitError>::null()
+            .try_into_string_nonempty()
+            .is_err());
+    }
+
+    #[test]
+    fn test_path() {
+        let field = FieldResult::<GdalTraitError>::some;
+
+        assert_eq!(
+            field(FieldValue::StringValue(
+                "tiles/12/2170/1155.png".to_string()
+            ))
+            .try_into_path()
+            .unwrap(),
+            Path::new("tiles/12/2170/1155.png")
+        );
+        assert!(field(FieldValue::StringValue(String::new()))
+            .try_into_path()
+            .is_err());
+        assert!(field(FieldValue::IntegerValue(1)).try_into_path().is_err());
+
+        assert_eq!(
+            field(FieldValue::StringValue(String::new()))
+                .try_into_path_opt()
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            FieldResult::<GdalTraitError>::null()
+                .try_into_path_opt()
+                .unwrap(),
+            None
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_system_time() {
+        let dt = |s: &str| {
+            FieldResult::<GdalTraitError>::some(FieldValue::DateTimeValue(
+                chrono::DateTime::parse_from_rfc3339(s).unwrap(),
+            ))
+        };
+
+        assert_eq!(
+            dt("1970-01-01T00:00:01.5+00:00")
+                .try_into_system_time()
+                .unwrap(),
+            UNIX_EPOCH + Duration::from_millis(1500)
+        );
+        assert_eq!(
+            dt("1970-01-01T02:00:00+02:00")
+                .try_into_system_time()
+                .unwrap(),
+            UNIX_EPOCH
+        );
+        // Before the epoch, with a fractional second.
+        assert_eq!(
+            dt("1969-12-31T23:59:58.25Z")
+                .try_into_system_time()
+                .unwrap(),
+            UNIX_EPOCH - Duration::from_millis(1750)
+        );
+
+        let null = FieldResult::<GdalTraitError>::null();
+        assert_eq!(null.try_into_system_time_opt().unwrap(), None);
+        assert!(
+            FieldResult::<GdalTraitError>::some(FieldValue::IntegerValue(0))
+                .try_into_system_time()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_bool_and_int64() {
+        let field = FieldResult::<GdalTraitError>::some;
+
+        assert!(field(FieldValue::IntegerValue(1)).try_into_bool().unwrap());
+        assert!(!field(FieldValue::IntegerValue(0)).try_into_bool().unwrap());
+        assert!(field(FieldValue::IntegerValue(2)).try_into_bool().is_err());
+        assert!(field(FieldValue::StringValue("true".to_string()))
+            .try_into_bool()
+            .is_err());
+        assert_eq!(
+            FieldResult::<GdalTraitError>::null()
+                .try_into_bool_opt()
+                .unwrap(),
+            None
+        );
+
+        assert_eq!(
+            field(FieldValue::IntegerValue(-7))
+                .try_into_int64()
+                .unwrap(),
+            -7
+        );
+        assert_eq!(
+            field(FieldValue::Integer64Value(i64::MAX))
+                .try_into_int64_opt()
+                .unwrap(),
+            Some(i64::MAX)
+        );
+        assert!(field(FieldValue::RealValue(1.0)).try_into_int64().is_err());
+    }
+
+    #[test]
+    fn test_number() {
+        let field = FieldResult::<GdalTraitError>::some;
+
+        assert_eq!(
+            field(FieldValue::IntegerValue(3))
+                .try_into_number()
+                .unwrap(),
+            3.0
+        );
+        assert_eq!(
+            field(FieldValue::Integer64Value(10_000_000_000))
+                .try_into_number()
+                .unwrap(),
+            1e10
+        );
+        assert_eq!(
+            field(FieldValue::RealValue(0.5)).try_into_number().unwrap(),
+            0.5
+        );
+        assert!(matches!(
+            field(FieldValue::StringValue("3".to_string())).try_into_number(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+
+        let null = FieldResult::<GdalTraitError>::null();
+        assert_eq!(null.try_into_number_opt().unwrap(), None);
+        assert!(matches!(
+            null.try_into_number(),
+            Err(GdalTraitError::NullField)
+        ));
+    }
+
+    #[test]
+    fn test_narrow_ints() {
+        let int = |v: i32| FieldResult::<GdalTraitError>::some(FieldValue::IntegerValue(v));
+
+        assert_eq!(int(-300).try_into_i16().unwrap(), -300);
+        assert_eq!(int(-128).try_into_i8().unwrap(), -128);
+        assert!(matches!(
+            int(40_000).try_into_i16(),
+            Err(GdalTraitError::InvalidFieldValue(msg)) if msg.starts_with("40000")
+        ));
+        assert!(int(128).try_into_i8().is_err());
+        assert_eq!(int(7).try_into_i8_opt().unwrap(), Some(7));
+
+        let null = FieldResult::<GdalTraitError>::null();
+        assert_eq!(null.try_into_i16_opt().unwrap(), None);
+        assert!(null.try_into_i8().is_err());
+    }
+
+    #[test]
+    fn test_string_list_raw() {
+        use crate::schema::{create_layer, FieldDefinition};
+        use gdal::vector::OGRwkbGeometryType;
+
+        let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+        let mut ds = driver.create_vector_only("").unwrap();
+        let layer = create_layer(
+            &mut ds,
+            "lists",
+            None,
+            OGRwkbGeometryType::wkbNone,
+            &[FieldDefinition::new("TAGS", OGRFieldType::OFTStringList)],
+        )
+        .unwrap();
+
+        let tags = vec!["a,b".to_string(), "c;d".to_string(), " e ".to_string()];
+        let mut feature = Feature::new(layer.defn()).unwrap();
+        feature
+            .set_field(0, &FieldValue::StringListValue(tags.clone()))
+            .unwrap();
+        feature.create(&layer).unwrap();
+
+        let feature = layer.feature(0).unwrap();
+        let field: FieldResult<GdalTraitError> = feature.field(0).into();
+        assert_eq!(field.try_into_string_list_raw().unwrap(), tags.as_slice());
+        assert_eq!(field.try_into_string_list().unwrap(), tags);
+
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("a,b".to_string()));
+        assert!(field.try_into_string_list_raw().is_err());
+    }
+
+    #[test]
+    fn test_string_trimmed() {
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue(" SE  \t".to_string()));
+        assert_eq!(field.try_into_string_trimmed().unwrap(), " SE");
+        assert_eq!(
+            field.try_into_string_trimmed_opt().unwrap().as_deref(),
+            Some(" SE")
+        );
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_string_trimmed_opt().unwrap(), None);
+        assert!(field.try_into_string_trimmed().is_err());
+    }
+
+    #[test]
+    fn test_list_nonempty_opt() {
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::IntegerListValue(vec![]));
+        assert_eq!(field.try_into_int_list_opt().unwrap(), Some(vec![]));
+        assert_eq!(field.try_into_int_list_nonempty_opt().unwrap(), None);
+
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::RealListValue(vec![1.0, 2.0]));
+        assert_eq!(
+            field.try_into_real_list_nonempty_opt().unwrap(),
+            Some(vec![1.0, 2.0])
+        );
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_string_list_nonempty_opt().unwrap(), None);
+    }
+
+    #[cfg(all(gdal_ge_3_7, feature = "chrono"))]
+    #[test]
+    fn test_date_time_iso() {
+        use chrono::Timelike;
+        use gdal::vector::OGRwkbGeometryType;
+
+        use crate::schema::{create_layer, FieldDefinition};
+
+        struct Timestamp(Option<DateTime<FixedOffset>>);
+
+        impl FromFeature<1, GdalTraitError> for Timestamp {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["AT"];
+            const ISO8601_DATE_TIMES: bool = true;
+
+            fn read(
+                _fid: Option<u64>,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                let [at] = fields;
+                Ok(Self(at.try_into_date_time_opt()?))
+            }
+        }
+
+        let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+        let mut ds = driver.create_vector_only("").unwrap();
+        let layer = create_layer(
+            &mut ds,
+            "timestamps",
+            None,
+            OGRwkbGeometryType::wkbNone,
+            &[FieldDefinition::new("AT", OGRFieldType::OFTDateTime)],
+        )
+        .unwrap();
+
+        let at = DateTime::parse_from_rfc3339("2023-05-01T12:34:56.789+02:00").unwrap();
+        let mut feature = Feature::new(layer.defn()).unwrap();
+        feature
+            .set_field(0, &FieldValue::DateTimeValue(at))
+            .unwrap();
+        feature.create(&layer).unwrap();
+        Feature::new(layer.defn()).unwrap().create(&layer).unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let timestamps = Timestamp::from_layer(&mut layer).unwrap();
+        let dt = timestamps[0].0.unwrap();
+        assert_eq!(dt, at);
+        assert_eq!(dt.offset().local_minus_utc(), 2 * 3600);
+        assert_eq!(dt.nanosecond(), 789_000_000);
+        assert_eq!(timestamps[1].0, None);
+
+        let dt = Timestamp::from_feature(layer.feature(0).unwrap())
+            .unwrap()
+            .0
+            .unwrap();
+        assert_eq!(dt.nanosecond(), 789_000_000);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_date_from_fields() {
+        let int = |v: i32| FieldResult::<GdalTraitError>::some(FieldValue::IntegerValue(v));
+        let null = FieldResult::<GdalTraitError>::null();
+
+        assert_eq!(
+            date_from_fields(&int(2019), &int(2), &int(28)).unwrap(),
+            NaiveDate::from_ymd_opt(2019, 2, 28).unwrap()
+        );
+        assert!(matches!(
+            date_from_fields(&int(2019), &int(2), &int(30)),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+        assert!(date_from_fields(&int(2019), &int(-1), &int(1)).is_err());
+        assert!(date_from_fields(&int(2019), &null, &int(1)).is_err());
+
+        assert_eq!(
+            date_from_fields_opt(&int(1850), &null, &null).unwrap(),
+            NaiveDate::from_ymd_opt(1850, 1, 1)
+        );
+        assert_eq!(
+            date_from_fields_opt(&int(1850), &int(6), &null).unwrap(),
+            NaiveDate::from_ymd_opt(1850, 6, 1)
+        );
+        assert_eq!(date_from_fields_opt(&null, &int(6), &int(1)).unwrap(), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_millis() {
+        let dt = DateTime::parse_from_rfc3339("2023-05-01T12:34:56.789+02:00").unwrap();
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::DateTimeValue(dt));
+        assert_eq!(field.try_into_timestamp_millis().unwrap(), 1682937296789);
+
+        let utc = DateTime::parse_from_rfc3339("2023-05-01T10:34:56.789Z").unwrap();
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::DateTimeValue(utc));
+        assert_eq!(field.try_into_timestamp_millis().unwrap(), 1682937296789);
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_timestamp_millis_opt().unwrap(), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_utc() {
+        let dt = DateTime::parse_from_rfc3339("2023-05-01T12:34:56+02:00").unwrap();
+        let field: FieldResult<GdalTraitError> = FieldResult::Some(FieldValue::DateTimeValue(dt));
+
+        let utc = field.try_into_utc().unwrap();
+        assert_eq!(utc.to_rfc3339(), "2023-05-01T10:34:56+00:00");
+        assert_eq!(utc, dt);
+        assert_eq!(field.try_into_utc_opt().unwrap(), Some(utc));
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_utc_opt().unwrap(), None);
+        assert!(field.try_into_utc().is_err());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_real_list_small() {
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::RealListValue(vec![1.0, 2.0]));
+        let small = field.try_into_real_list_small::<4>().unwrap();
+        assert_eq!(small.as_slice(), &[1.0, 2.0]);
+        assert!(!small.spilled());
+
+        let small = field.try_into_real_list_small::<1>().unwrap();
+        assert_eq!(small.as_slice(), &[1.0, 2.0]);
+        assert!(small.spilled());
+    }
+
+    struct CountryLayer {
+        layer_name: String,
+        name: String,
+    }
+
+    impl FromFeatureContext<1, TestError> for CountryLayer {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME"];
+
+        type Context = String;
+
+        fn make_context(layer: &Layer) -> Result<Self::Context, TestError> {
+            Ok(layer.name())
+        }
+
+        fn read_ctx(
+            ctx: &Self::Context,
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            let [name_field] = fields;
+
+            Ok(Self {
+                layer_name: ctx.clone(),
+                name: name_field.try_into_string()?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_from_layer_ctx() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let countries = CountryLayer::from_layer_ctx(&mut layer).unwrap();
+
+        assert_eq!(countries.len(), 177);
+        assert_eq!(countries[110].name, "Sweden");
+        assert!(countries
+            .iter()
+            .all(|c| c.layer_name == "ne_110m_admin_0_countries"));
+
+        struct PopYear(Option<i32>);
+
+        impl FromFeatureContext<1, TestError> for PopYear {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["POP_YEAR"];
+            const NULL_SENTINELS: [Option<NullSentinel>; Self::NUM_FIELDS] =
+                [Some(NullSentinel::Integer(2019))];
+
+            type Context = ();
+
+            fn make_context(_layer: &Layer) -> Result<Self::Context, TestError> {
+                Ok(())
+            }
+
+            fn read_ctx(
+                _ctx: &Self::Context,
+                _fid: Option<u64>,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, TestError> {
+                let [pop_year_field] = fields;
+                Ok(Self(pop_year_field.try_into_int_opt()?))
+            }
+        }
+
+        let years = PopYear::from_layer_ctx(&mut layer).unwrap();
+        assert_eq!(years[110].0, None);
+    }
+
+    #[cfg(feature = "semver")]
+    #[test]
+    fn test_semver() {
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("1.2.3-beta.1".to_string()));
+        let version = field.try_into_semver().unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (1, 2, 3));
+
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("1.2".to_string()));
+        assert!(matches!(
+            field.try_into_semver(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.try_into_semver_opt().unwrap(), None);
+    }
+
+    struct BadCountry;
+
+    impl FromFeature<2, TestError> for BadCountry {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME", "NOT_A_FIELD"];
+
+        const FIELD_TYPES: Option<[OGRFieldType::Type; Self::NUM_FIELDS]> =
+            Some([OGRFieldType::OFTInteger, OGRFieldType::OFTString]);
+
+        fn read(
+            _fid: Option<u64>,
+            _fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            Ok(Self)
+        }
+    }
+
+    #[test]
+    fn test_check_schema() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let layer = ds.layer(0).unwrap();
+
+        assert_eq!(Country::check_schema(&layer), Ok(()));
+        assert_eq!(
+            BadCountry::check_schema(&layer),
+            Err(vec![
+                SchemaIssue::TypeMismatch {
+                    field: "NAME".to_string(),
+                    expected: OGRFieldType::OFTInteger,
+                    found: OGRFieldType::OFTString,
+                },
+                SchemaIssue::MissingField("NOT_A_FIELD".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_typed_features() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let swedes: Vec<Country> = Country::typed_features(&mut layer)
+            .filter_map(Result::ok)
+            .filter(|country| country.iso_a3 == "SWE")
+            .collect();
+
+        assert_eq!(swedes.len(), 1);
+        assert_eq!(swedes[0].name, "Sweden");
+    }
+
+    #[test]
+    fn test_try_into_method_names() {
+        let null: FieldResult<GdalTraitError> = FieldResult::Null;
+
+        assert!(null.try_into_int().is_err());
+        assert!(null.try_into_int_list().is_err());
+        assert!(null.try_into_int64().is_err());
+        assert!(null.try_into_int64_list().is_err());
+        assert!(null.try_into_string().is_err());
+        assert!(null.try_into_string_list().is_err());
+        assert!(null.try_into_real().is_err());
+        assert!(null.try_into_real_list().is_err());
+
+        assert_eq!(null.try_into_int_opt().unwrap(), None);
+        assert_eq!(null.try_into_int_list_opt().unwrap(), None);
+        assert_eq!(null.try_into_int64_opt().unwrap(), None);
+        assert_eq!(null.try_into_int64_list_opt().unwrap(), None);
+        assert_eq!(null.try_into_string_opt().unwrap(), None);
+        assert_eq!(null.try_into_string_list_opt().unwrap(), None);
+        assert_eq!(null.try_into_real_opt().unwrap(), None);
+        assert_eq!(null.try_into_real_list_opt().unwrap(), None);
+
+        assert_eq!(null.try_into_int_list_nonempty_opt().unwrap(), None);
+        assert_eq!(null.try_into_int64_list_nonempty_opt().unwrap(), None);
+        assert_eq!(null.try_into_string_list_nonempty_opt().unwrap(), None);
+        assert_eq!(null.try_into_real_list_nonempty_opt().unwrap(), None);
+
+        #[cfg(feature = "chrono")]
+        {
+            assert!(null.try_into_date().is_err());
+            assert!(null.try_into_date_time().is_err());
+            assert_eq!(null.try_into_date_opt().unwrap(), None);
+            assert_eq!(null.try_into_date_time_opt().unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_for_each_in_layer() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let mut names = Vec::new();
+        Country::for_each_in_layer(&mut layer, |country| {
+            names.push(country.name);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(names.len(), 177);
+
+        // Stops at the first callback error.
+        let mut count = 0;
+        let res = Country::for_each_in_layer(&mut layer, |_| {
+            count += 1;
+            match count {
+                3 => Err(TestError::NoGeometry),
+                _ => Ok(()),
+            }
+        });
+        assert!(matches!(res, Err(TestError::NoGeometry)));
+        assert_eq!(count, 3);
+    }
+
+    struct MissingField;
+
+    impl FromFeature<1, TestError> for MissingField {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NOT_A_FIELD"];
+
+        fn read(
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            let [field] = fields;
+            field.into_res()?;
+            Ok(Self)
+        }
+    }
+
+    #[test]
+    fn test_from_layer_assume_present() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let countries = Country::from_layer_assume_present(&mut layer).unwrap();
+        assert_eq!(countries, Country::from_layer(&mut layer).unwrap());
+
+        assert!(matches!(
+            MissingField::from_layer_assume_present(&mut layer),
+            Err(TestError::GdalTraitError(GdalTraitError::WithContext {
+                fid: None,
+                ..
+            }))
+        ));
+
+        // The third station has no elevation.
+        struct Elevation;
+
+        impl FromFeature<2, GdalTraitError> for Elevation {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["name", "elevation"];
+
+            fn read(
+                _fid: Option<u64>,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                assert!(fields
+                    .iter()
+                    .all(|field| matches!(field, FieldResult::Some(_))));
+                Ok(Self)
+            }
+        }
+
+        let ds = Dataset::open("fixtures/stations/stations.gpkg").unwrap();
+        let mut layer = ds.layer_by_name("stations").unwrap();
+        let err = Elevation::from_layer_assume_present(&mut layer)
+            .err()
+            .unwrap();
+        assert!(err.is_null_field());
+        assert!(matches!(
+            err,
+            GdalTraitError::WithContext { fid: Some(3), .. }
+        ));
+    }
+
+    #[test]
+    fn test_fields_by_name() {
+        const NAMES: [&str; 2] = ["NAME", "POP_EST"];
+        let fields = Fields::new(
+            &NAMES,
+            [
+                FieldResult::some(FieldValue::StringValue("Sweden".to_string())),
+                FieldResult::null(),
+            ],
+        );
+
+        assert_eq!(
+            fields.by_name("NAME").unwrap().try_into_string().unwrap(),
+            "Sweden"
+        );
+        assert_eq!(
+            fields
+                .by_name("POP_EST")
+                .unwrap()
+                .try_into_real_opt()
+                .unwrap(),
+            None
+        );
+        assert!(matches!(
+            fields.by_name("POP_YEAR"),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+        assert!(fields.get(1).is_some());
+        assert!(fields.get(2).is_none());
+    }
+
+    #[test]
+    fn test_from_fids() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let names: Vec<String> = Country::from_fids(&mut layer, &[142, 1000, 110])
+            .unwrap()
+            .into_iter()
+            .map(|country| country.name)
+            .collect();
+        assert_eq!(names, vec!["Denmark", "Sweden"]);
+
+        assert!(matches!(
+            Country::from_fids_strict(&mut layer, &[142, 1000]),
+            Err(TestError::GdalTraitError(GdalTraitError::WithContext { source, .. }))
+                if matches!(*source, GdalTraitError::FeatureNotFound(1000))
+        ));
+    }
+
+    #[test]
+    fn test_from_fids_sequential() {
+        struct Name(String);
+
+        impl FromFeature<1, GdalTraitError> for Name {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["name"];
+
+            fn read(
+                _fid: Option<u64>,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                let [name] = fields;
+                Ok(Self(name.try_into_string()?))
+            }
+        }
+
+        let geojsonseq = ["Gävle", "Sandviken", "Hofors", "Ockelbo", "Ovanåker"]
+            .map(|name| {
+                format!(r#"{{"type":"Feature","properties":{{"name":"{name}"}},"geometry":null}}"#)
+            })
+            .join("\n");
+        gdal::vsi::create_mem_file("/vsimem/from_fids.geojsonl", geojsonseq.into_bytes()).unwrap();
+
+        let ds = Dataset::open("/vsimem/from_fids.geojsonl").unwrap();
+        let mut layer = ds.layer(0).unwrap();
+        assert!(!layer_has_capability(&layer, "RandomRead"));
+
+        let fids: Vec<u64> = layer.features().filter_map(|f| f.fid()).collect();
+        let names = |items: Vec<Name>| items.into_iter().map(|n| n.0).collect::<Vec<_>>();
+
+        // Sequential, stopping after the last FID.
+        assert_eq!(
+            names(Name::from_fids(&mut layer, &[fids[1], fids[3], 1000]).unwrap()),
+            vec!["Sandviken", "Ockelbo"]
+        );
+        // Fetched one by one.
+        assert_eq!(
+            names(Name::from_fids(&mut layer, &[fids[4], fids[0], fids[0]]).unwrap()),
+            vec!["Ovanåker", "Gävle", "Gävle"]
+        );
+
+        // Spatial filters are ignored by fetches, so also by the sequential read, and kept.
+        layer.set_spatial_filter_rect(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(
+            names(Name::from_fids(&mut layer, &[fids[1], fids[3]]).unwrap()),
+            vec!["Sandviken", "Ockelbo"]
+        );
+        assert_eq!(layer.features().count(), 0);
+        layer.clear_spatial_filter();
+
+        // The attribute filter can't be lifted temporarily, so applies to the sequential read.
+        layer.set_attribute_filter("name = 'Ockelbo'").unwrap();
+        assert_eq!(
+            names(Name::from_fids(&mut layer, &[fids[1], fids[3]]).unwrap()),
+            vec!["Ockelbo"]
+        );
+        assert_eq!(layer.features().count(), 1);
+        layer.clear_attribute_filter();
+
+        drop(layer);
+        drop(ds);
+        gdal::vsi::unlink_mem_file("/vsimem/from_fids.geojsonl").unwrap();
+    }
+
+    #[test]
+    fn test_from_layer_with() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        assert_eq!(
+            Country::from_layer_with(&mut layer, &ReadOptions::default()).unwrap(),
+            Country::from_layer(&mut layer).unwrap()
+        );
+
+        let options = ReadOptions::default()
+            .with_attribute_filter("CONTINENT = 'Europe'")
+            .with_limit(100);
+        assert_eq!(
+            Country::from_layer_with(&mut layer, &options)
+                .unwrap()
+                .len(),
+            39
+        );
+
+        let options = ReadOptions::default().with_limit(3);
+        assert_eq!(
+            Country::from_layer_with(&mut layer, &options)
+                .unwrap()
+                .len(),
+            3
+        );
+
+        // Filters don't outlive the read.
+        assert_eq!(layer.feature_count(), 177);
+
+        let options = ReadOptions::default()
+            .with_bbox((15.0, 60.0, 15.1, 60.1))
+            .with_target_srs(SpatialRef::from_epsg(3857).unwrap())
+            .with_trim_strings(true);
+        let countries = Country::from_layer_with(&mut layer, &options).unwrap();
+        // Without GEOS, features are matched by their envelope, which includes Norway.
+        let sweden = countries.iter().find(|c| c.name == "Sweden").unwrap();
+        let unprojected = Country::from_fids(&mut layer, &[110]).unwrap();
+        assert_ne!(sweden.geom, unprojected[0].geom);
+
+        let not_a_field = ReadOptions::default().with_attribute_filter("NOT_A_FIELD = 1");
+        assert!(Country::from_layer_with(&mut layer, &not_a_field).is_err());
+        assert_eq!(layer.feature_count(), 177);
+
+        // Filters set on the layer apply, and are kept unless replaced.
+        layer.set_attribute_filter("CONTINENT = 'Europe'").unwrap();
+        assert_eq!(Country::from_layer(&mut layer).unwrap().len(), 39);
+        let options = ReadOptions::default().with_bbox((15.0, 60.0, 15.1, 60.1));
+        let countries = Country::from_layer_with(&mut layer, &options).unwrap();
+        assert!(!countries.is_empty() && countries.len() < 39);
+        assert_eq!(layer.feature_count(), 39);
+        layer.clear_attribute_filter();
+
+        // A replaced spatial filter is restored afterwards.
+        layer.set_spatial_filter_rect(15.0, 60.0, 15.1, 60.1);
+        let filtered = layer.feature_count();
+        assert!(filtered < 177);
+        let options = ReadOptions::default().with_bbox((-180.0, -90.0, 180.0, 90.0));
+        assert_eq!(
+            Country::from_layer_with(&mut layer, &options)
+                .unwrap()
+                .len(),
+            177
+        );
+        assert_eq!(layer.feature_count(), filtered);
+        layer.clear_spatial_filter();
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[cfg(feature = "chrono")]
+    struct Station {
+        name: String,
+        elevation: Option<f64>,
+        visitors: Option<i64>,
+        platforms: Option<i64>,
+        staffed: Option<bool>,
+        opened: Option<DateTime<FixedOffset>>,
+        surveyed: Option<NaiveDate>,
+        has_geometry: bool,
+    }
+
+    #[cfg(feature = "chrono")]
+    impl FromFeature<7, GdalTraitError> for Station {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = [
+            "name",
+            "elevation",
+            "visitors",
+            "platforms",
+            "staffed",
+            "opened",
+            "surveyed",
+        ];
+
+        fn read(
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            geometry: Option<&Geometry>,
+        ) -> Result<Self, GdalTraitError> {
+            let [name, elevation, visitors, platforms, staffed, opened, surveyed] = fields;
+
+            Ok(Self {
+                name: name.try_into_string()?,
+                elevation: elevation.try_into_real_opt()?,
+                visitors: visitors.try_into_int64_opt()?,
+                platforms: platforms.try_into_int64_opt()?,
+                staffed: staffed.try_into_bool_opt()?,
+                opened: opened.try_into_date_time_opt()?,
+                surveyed: surveyed.try_into_date_opt()?,
+                has_geometry: geometry.is_some(),
+            })
+        }
+    }
+
+    #[cfg(gdal_ge_3_2)]
+    #[test]
+    fn test_match_aliases() {
+        struct Elevation(Option<f64>);
+
+        impl FromFeature<1, GdalTraitError> for Elevation {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["Elevation (m)"];
+            const MATCH_ALIASES: bool = true;
+
+            fn read(
+                _fid: Option<u64>,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                let [elevation] = fields;
+                Ok(Self(elevation.try_into_real_opt()?))
+            }
+        }
+
+        let ds = Dataset::open("fixtures/stations/stations.gpkg").unwrap();
+        let mut layer = ds.layer_by_name("stations").unwrap();
+
+        assert_eq!(Elevation::check_schema(&layer), Ok(()));
+
+        let aliases = crate::layer_field_aliases(&layer);
+        assert!(aliases.contains(&("name".to_string(), None)));
+        assert!(aliases.contains(&("elevation".to_string(), Some("Elevation (m)".to_string()))));
+
+        let elevations: Vec<Option<f64>> = Elevation::from_layer(&mut layer)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.0)
+            .collect();
+        assert_eq!(elevations, vec![Some(12.5), Some(78.0), None]);
+
+        let first = Elevation::from_feature(layer.feature(1).unwrap()).unwrap();
+        assert_eq!(first.0, Some(12.5));
+    }
+
+    // GeoPackages have genuine NULLs and richer field types than shapefiles.
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_layer_with_gpkg() {
+        let ds = Dataset::open("fixtures/stations/stations.gpkg").unwrap();
+        let mut layer = ds.layer_by_name("stations").unwrap();
+
+        let defn = layer.defn();
+        let field_type = |name: &str| {
+            let idx = defn.field_index(name).unwrap();
+            defn.fields().nth(idx).unwrap().field_type()
+        };
+        assert_eq!(field_type("visitors"), OGRFieldType::OFTInteger64);
+        assert_eq!(field_type("platforms"), OGRFieldType::OFTInteger);
+
+        let stations = Station::from_layer_with(&mut layer, &ReadOptions::default()).unwrap();
+        let offset = |hours| FixedOffset::east_opt(hours * 3600).unwrap();
+        assert_eq!(
+            stations,
+            vec![
+                Station {
+                    name: "Gävle C".to_string(),
+                    elevation: Some(12.5),
+                    visitors: Some(5_000_000_000),
+                    platforms: Some(4),
+                    staffed: Some(true),
+                    opened: Some(offset(2).with_ymd_and_hms(2019, 6, 1, 8, 30, 0).unwrap()),
+                    surveyed: NaiveDate::from_ymd_opt(2023, 9, 12),
+                    has_geometry: true,
+                },
+                Station {
+                    name: "Sandviken".to_string(),
+                    elevation: Some(78.0),
+                    visitors: Some(42),
+                    platforms: Some(2),
+                    staffed: Some(false),
+                    opened: Some(offset(0).with_ymd_and_hms(2020, 1, 15, 12, 0, 0).unwrap()),
+                    surveyed: NaiveDate::from_ymd_opt(2023, 9, 13),
+                    has_geometry: true,
+                },
+                Station {
+                    name: "Unnamed halt".to_string(),
+                    elevation: None,
+                    visitors: None,
+                    platforms: None,
+                    staffed: None,
+                    opened: None,
+                    surveyed: None,
+                    has_geometry: false,
+                },
+            ]
+        );
+
+        // The offset is kept rather than normalized.
+        assert_eq!(stations[0].opened.unwrap().offset().local_minus_utc(), 7200);
+
+        let options = ReadOptions::default()
+            .with_attribute_filter("staffed = 1")
+            .with_skip_null_geometry(true);
+        let staffed = Station::from_layer_with(&mut layer, &options).unwrap();
+        assert_eq!(staffed.len(), 1);
+        assert_eq!(staffed[0].name, "Gävle C");
+    }
+
+    #[test]
+    fn test_from_layer_with_linearize() {
+        use crate::schema::create_layer;
+        use gdal::vector::OGRwkbGeometryType;
+
+        struct Parcel {
+            geom: Geometry,
+        }
+
+        impl FromFeature<0, GdalTraitError> for Parcel {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = [];
+
+            fn read(
+                _fid: Option<u64>,
+                _fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                Ok(Self {
+                    geom: geometry.ok_or(GdalTraitError::NullField)?.clone(),
+                })
+            }
+        }
+
+        let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+        let mut ds = driver.create_vector_only("").unwrap();
+        let mut layer = create_layer(
+            &mut ds,
+            "parcels",
+            None,
+            OGRwkbGeometryType::wkbCurvePolygon,
+            &[],
+        )
+        .unwrap();
+        let mut feature = Feature::new(layer.defn()).unwrap();
+        feature
+            .set_geometry(
+                Geometry::from_wkt("CURVEPOLYGON (CIRCULARSTRING (0 0, 2 0, 0 0))").unwrap(),
+            )
+            .unwrap();
+        feature.create(&layer).unwrap();
+
+        let parcels = Parcel::from_layer(&mut layer).unwrap();
+        assert_eq!(
+            parcels[0].geom.geometry_type(),
+            OGRwkbGeometryType::wkbCurvePolygon
+        );
+
+        let options = ReadOptions::default().with_linearize(0.0);
+        let parcels = Parcel::from_layer_with(&mut layer, &options).unwrap();
+        assert_eq!(
+            parcels[0].geom.geometry_type(),
+            OGRwkbGeometryType::wkbPolygon
+        );
+    }
+
+    #[test]
+    fn test_skip_null_geometry() {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": {
+                        "NAME": "Sweden", "ISO_A2": "SE", "ISO_A3": "SWE",
+                        "POP_EST": 10285453.0, "POP_YEAR": 2019
+                    },
+                    "geometry": { "type": "Point", "coordinates": [18.07, 59.33] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": {
+                        "NAME": "Atlantis", "ISO_A2": null, "ISO_A3": "ATL",
+                        "POP_EST": 0.0, "POP_YEAR": 2019
+                    },
+                    "geometry": null
+                }
+            ]
+        }"#;
+        gdal::vsi::create_mem_file("/vsimem/null_geometry.geojson", geojson.as_bytes().to_vec())
+            .unwrap();
+
+        let ds = Dataset::open("/vsimem/null_geometry.geojson").unwrap();
+        let mut layer = ds.layer(0).unwrap();
+        assert!(matches!(
+            Country::from_layer(&mut layer),
+            Err(TestError::NoGeometry)
+        ));
+
+        let options = ReadOptions::default()
+            .with_skip_null_geometry(true)
+            .with_limit(1);
+        let countries = Country::from_layer_with(&mut layer, &options).unwrap();
+        assert_eq!(countries.len(), 1);
+        assert_eq!(countries[0].name, "Sweden");
+
+        drop(layer);
+        drop(ds);
+        gdal::vsi::unlink_mem_file("/vsimem/null_geometry.geojson").unwrap();
+    }
+
+    #[test]
+    fn test_from_layer_intersecting() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
 
-        #[error("No Geomety")]
-        NoGeometry,
+        // A small triangle in central Sweden, within the envelope of Norway.
+        let area = Geometry::from_wkt("POLYGON ((14 60, 16 60, 14 62, 14 60))").unwrap();
+        let names: Vec<String> = Country::from_layer_intersecting(&mut layer, &area)
+            .unwrap()
+            .into_iter()
+            .map(|country| country.name)
+            .collect();
+        assert_eq!(names, vec!["Sweden"]);
 
-        #[error("geo_type Error: {0}")]
-        GeoError(#[from] geo_types::Error),
+        assert_eq!(layer.feature_count(), 177);
+
+        // A spatial filter set beforehand is still active afterwards.
+        layer.set_spatial_filter_rect(-30.0, 30.0, 60.0, 75.0);
+        let filtered = layer.feature_count();
+        assert!(filtered < 177);
+        assert_eq!(
+            Country::from_layer_intersecting(&mut layer, &area)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(layer.feature_count(), filtered);
+        layer.clear_spatial_filter();
     }
 
-    #[derive(Debug, PartialEq)]
-    struct Country {
-        name: String,
+    struct Population {
+        pop_year: Option<i32>,
         iso_a2: Option<String>,
-        iso_a3: String,
+    }
 
-        pop_est: f64,
-        pop_year: i32,
+    impl FromFeature<2, TestError> for Population {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["POP_YEAR", "ISO_A2"];
 
-        geom: geo_types::Geometry<f64>,
+        // The fixture uses -99 for missing codes, 2019 is just the most common year.
+        const NULL_SENTINELS: [Option<NullSentinel>; Self::NUM_FIELDS] = [
+            Some(NullSentinel::Integer(2019)),
+            Some(NullSentinel::String("-99")),
+        ];
+
+        fn read(
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            let [pop_year_field, a2_field] = fields;
+
+            Ok(Self {
+                pop_year: pop_year_field.try_into_int_opt()?,
+                iso_a2: a2_field.try_into_string_opt()?,
+            })
+        }
     }
 
-    impl FromFeature<5, TestError> for Country {
-        const FIELDS: [&'static str; Self::NUM_FIELDS] =
-            ["NAME", "ISO_A2", "ISO_A3", "POP_EST", "POP_YEAR"];
+    #[test]
+    fn test_null_sentinels() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let populations = Population::from_layer(&mut layer).unwrap();
+        assert_eq!(populations[110].pop_year, None);
+        assert_eq!(populations[110].iso_a2.as_deref(), Some("SE"));
+        assert!(populations.iter().any(|p| p.iso_a2.is_none()));
+
+        let sweden = Population::from_feature(layer.feature(110).unwrap()).unwrap();
+        assert_eq!(sweden.pop_year, None);
+    }
+
+    #[test]
+    fn test_from_layer_random() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let layer = ds.layer(0).unwrap();
+
+        let countries = Country::from_layer_random(&layer).unwrap();
+        assert_eq!(countries.len(), 177);
+        assert_eq!(countries[110].name, "Sweden");
+
+        let mut layer = layer;
+        assert_eq!(countries, Country::from_layer(&mut layer).unwrap());
+
+        layer.set_spatial_filter_rect(15.0, 60.0, 15.1, 60.1);
+        let unsupported = |layer: &Layer| match Country::from_layer_random(layer) {
+            Err(TestError::GdalTraitError(GdalTraitError::WithContext { source, .. })) => {
+                matches!(*source, GdalTraitError::UnsupportedLayer(_))
+            }
+            _ => false,
+        };
+        assert!(unsupported(&layer));
+        layer.clear_spatial_filter();
+
+        gdal::vsi::create_mem_file(
+            "/vsimem/from_layer_random.geojsonl",
+            br#"{"type":"Feature","properties":{},"geometry":null}"#.to_vec(),
+        )
+        .unwrap();
+        let seq_ds = Dataset::open("/vsimem/from_layer_random.geojsonl").unwrap();
+        assert!(unsupported(&seq_ds.layer(0).unwrap()));
+        drop(seq_ds);
+        gdal::vsi::unlink_mem_file("/vsimem/from_layer_random.geojsonl").unwrap();
+    }
+
+    #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct Continent {
+        name: Arc<str>,
+    }
+
+    impl FromFeature<1, TestError> for Continent {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["CONTINENT"];
 
         fn read(
             _fid: Option<u64>,
             fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
-            geometry: Option<&Geometry>,
+            _geometry: Option<&Geometry>,
         ) -> Result<Self, TestError> {
-            let [name_field, a2_field, a3_field, pop_est_field, pop_year_field] = fields;
+            let [continent_field] = fields;
+            Ok(Self {
+                name: continent_field.try_into_string()?.into(),
+            })
+        }
 
-            let name = name_field.try_into_string()?;
-            let iso_a2 = a2_field.try_into_string_opt()?;
-            let iso_a3 = a3_field.try_into_string()?;
+        fn read_interned(
+            _feature: &Feature,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            interner: &mut StringInterner,
+        ) -> Result<Self, TestError> {
+            let [continent_field] = fields;
+            Ok(Self {
+                name: continent_field.try_into_interned(interner)?,
+            })
+        }
+    }
 
-            let pop_est = pop_est_field.try_into_real()?;
-            let pop_year = pop_year_field.try_into_int()?;
+    #[test]
+    fn test_from_layer_collect() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
 
-            let geom = geometry.ok_or(TestError::NoGeometry)?.to_geo()?;
+        let continents: HashSet<Continent> = Continent::from_layer_collect(&mut layer).unwrap();
+        assert_eq!(continents.len(), 8);
+
+        let sorted: BTreeSet<Continent> = Continent::from_layer_collect(&mut layer).unwrap();
+        assert_eq!(&*sorted.first().unwrap().name, "Africa");
+
+        let boxed: Box<[Continent]> = Continent::from_layer_collect(&mut layer).unwrap();
+        assert_eq!(boxed.len(), 177);
+    }
+
+    #[test]
+    fn test_from_layer_ignores_other_fields() {
+        // Reads a field outside of FIELDS straight from the feature.
+        struct NameAndContinent {
+            name: String,
+            continent: Option<String>,
+        }
+
+        impl FromFeature<1, GdalTraitError> for NameAndContinent {
+            // Matched case-insensitively, like by GDAL.
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["name"];
+
+            fn read(
+                _fid: Option<u64>,
+                _fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                unreachable!()
+            }
+
+            fn read_with_feature(
+                feature: &Feature,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            ) -> Result<Self, GdalTraitError> {
+                let [name_field] = fields;
+                let idx = feature.field_index("CONTINENT")?;
+                Ok(Self {
+                    name: name_field.try_into_string()?,
+                    continent: feature.field_as_string(idx)?,
+                })
+            }
+        }
+
+        struct AllFields(NameAndContinent);
+
+        impl FromFeature<1, GdalTraitError> for AllFields {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = NameAndContinent::FIELDS;
+            const IGNORE_OTHER_FIELDS: bool = false;
+
+            fn read(
+                _fid: Option<u64>,
+                _fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+                _geometry: Option<&Geometry>,
+            ) -> Result<Self, GdalTraitError> {
+                unreachable!()
+            }
+
+            fn read_with_feature(
+                feature: &Feature,
+                fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            ) -> Result<Self, GdalTraitError> {
+                NameAndContinent::read_with_feature(feature, fields).map(Self)
+            }
+        }
+
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let countries = NameAndContinent::from_layer(&mut layer).unwrap();
+        assert_eq!(countries[110].name, "Sweden");
+        assert!(countries.iter().all(|c| c.continent.is_none()));
+
+        // The same for the other readers.
+        let countries = NameAndContinent::from_fids(&mut layer, &[110]).unwrap();
+        assert_eq!(countries[0].name, "Sweden");
+        assert_eq!(countries[0].continent, None);
+        let countries = NameAndContinent::from_layer_sorted_by(&mut layer, "CONTINENT").unwrap();
+        assert!(countries.iter().all(|c| c.continent.is_none()));
+
+        // Not ignored outside of the from_layer family, or when opted out.
+        let sweden = NameAndContinent::from_feature(layer.feature(110).unwrap()).unwrap();
+        assert_eq!(sweden.continent.as_deref(), Some("Europe"));
+        let countries = AllFields::from_layer(&mut layer).unwrap();
+        assert_eq!(countries[110].0.continent.as_deref(), Some("Europe"));
+
+        // Fields ignored beforehand stay ignored, and are restored afterwards.
+        let pop_est_idx = layer.defn().field_index("POP_EST").unwrap();
+        let ignored = IgnoredFields::set(&layer, ["POP_EST"]).unwrap();
+        NameAndContinent::from_layer(&mut layer).unwrap();
+        let sweden = layer.feature(110).unwrap();
+        assert_eq!(sweden.field(pop_est_idx).unwrap(), None);
+        assert!(sweden.field_as_string(0).unwrap().is_some());
+
+        drop(ignored);
+        let sweden = layer.feature(110).unwrap();
+        assert!(sweden.field(pop_est_idx).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_from_layer_interned() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let mut interner = StringInterner::new();
+        let continents = Continent::from_layer_interned(&mut layer, &mut interner).unwrap();
+        assert_eq!(continents.len(), 177);
+        assert_eq!(interner.len(), 8);
+
+        // Tanzania and W. Sahara
+        assert_eq!(&*continents[1].name, "Africa");
+        assert!(Arc::ptr_eq(&continents[1].name, &continents[2].name));
+
+        let plain = Continent::from_layer(&mut layer).unwrap();
+        assert!(!Arc::ptr_eq(&plain[1].name, &plain[2].name));
+    }
+
+    #[test]
+    fn test_error_context() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let Err(TestError::GdalTraitError(GdalTraitError::WithContext {
+            fid,
+            layer: layer_name,
+            source,
+        })) = MissingField::from_layer(&mut layer)
+        else {
+            panic!("Expected an error with context");
+        };
+        assert_eq!(fid, Some(0));
+        assert_eq!(layer_name.as_deref(), Some("ne_110m_admin_0_countries"));
+        assert!(matches!(*source, GdalTraitError::GdalError(_)));
+    }
+
+    #[test]
+    fn test_from_layer_sorted_by() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let countries = Country::from_layer_sorted_by(&mut layer, "POP_EST").unwrap();
+        assert_eq!(countries.len(), 177);
+        assert_eq!(countries[0].name, "Fr. S. Antarctic Lands");
+        assert_eq!(countries[176].name, "China");
+        assert!(countries.windows(2).all(|w| w[0].pop_est <= w[1].pop_est));
+
+        let by_sql =
+            Country::from_dataset_sorted_by(&ds, "ne_110m_admin_0_countries", "POP_EST").unwrap();
+        assert_eq!(by_sql, countries);
+
+        assert!(Country::from_layer_sorted_by(&mut layer, "NOT_A_FIELD").is_err());
+        assert!(
+            Country::from_dataset_sorted_by(&ds, "ne_110m_admin_0_countries", "NOT_A_FIELD")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_compare_sort_keys() {
+        let one = Some(FieldValue::IntegerValue(1));
+        let half = Some(FieldValue::RealValue(0.5));
+        assert_eq!(compare_sort_keys(&half, &one), Ordering::Less);
+        assert_eq!(compare_sort_keys(&None, &one), Ordering::Greater);
+        assert_eq!(compare_sort_keys(&one, &None), Ordering::Less);
+        assert_eq!(compare_sort_keys(&None, &None), Ordering::Equal);
+
+        let a = Some(FieldValue::StringValue("a".to_string()));
+        assert_eq!(compare_sort_keys(&a, &one), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_equals_filter() {
+        assert_eq!(
+            equals_filter(
+                "NAME",
+                &FieldValue::StringValue("Côte d'Ivoire".to_string())
+            )
+            .unwrap(),
+            r#""NAME" = 'Côte d''Ivoire'"#
+        );
+        assert_eq!(
+            equals_filter("POP_YEAR", &FieldValue::IntegerValue(2017)).unwrap(),
+            r#""POP_YEAR" = 2017"#
+        );
+        assert_eq!(
+            equals_filter("POP_EST", &FieldValue::RealValue(0.5)).unwrap(),
+            r#""POP_EST" = 0.5"#
+        );
+        assert!(equals_filter("POP_EST", &FieldValue::RealValue(f64::NAN)).is_err());
+        assert!(equals_filter("TAGS", &FieldValue::IntegerListValue(vec![1])).is_err());
+    }
+
+    #[test]
+    fn test_from_layer_by_attr() {
+        // An indexed copy of the fixture, so that the index is never written next to it.
+        let source = "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries";
+        let target = "/vsimem/indexed/ne_110m_admin_0_countries";
+        for extension in ["shp", "shx", "dbf", "prj", "cpg"] {
+            let bytes = std::fs::read(format!("{source}.{extension}")).unwrap();
+            gdal::vsi::create_mem_file(&format!("{target}.{extension}"), bytes).unwrap();
+        }
+
+        let ds = Dataset::open_ex(
+            format!("{target}.shp"),
+            gdal::DatasetOptions {
+                open_flags: gdal::GdalOpenFlags::GDAL_OF_UPDATE
+                    | gdal::GdalOpenFlags::GDAL_OF_VECTOR,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        ds.execute_sql(
+            "CREATE INDEX ON ne_110m_admin_0_countries USING ISO_A3",
+            None,
+            Dialect::DEFAULT,
+        )
+        .unwrap();
+        assert!(gdal::vsi::read_dir("/vsimem/indexed", false)
+            .unwrap()
+            .iter()
+            .any(|path| path.extension().is_some_and(|ext| ext == "ind")));
+
+        let mut layer = ds.layer(0).unwrap();
+        let sweden = Country::from_layer_by_attr(
+            &mut layer,
+            "ISO_A3",
+            &FieldValue::StringValue("SWE".into()),
+        )
+        .unwrap();
+        assert_eq!(sweden.len(), 1);
+        assert_eq!(sweden[0].name, "Sweden");
+
+        // Only a few features are read through the index, against all of them without one.
+        let features_read =
+            |layer: &Layer| unsafe { gdal_sys::OGR_L_GetFeaturesRead(layer.c_layer()) };
+        let before = features_read(&layer);
+        Country::from_layer_by_attr(&mut layer, "ISO_A3", &FieldValue::StringValue("SWE".into()))
+            .unwrap();
+        let indexed = features_read(&layer) - before;
+
+        let before = features_read(&layer);
+        let unindexed = Country::from_layer_by_attr(
+            &mut layer,
+            "NAME",
+            &FieldValue::StringValue("Sweden".into()),
+        )
+        .unwrap();
+        let scanned = features_read(&layer) - before;
+
+        assert_eq!(unindexed, sweden);
+        assert!(scanned >= 177);
+        assert!(
+            indexed * 10 < scanned,
+            "read {indexed} features through the index"
+        );
+
+        let ivory_coast = Country::from_layer_by_attr(
+            &mut layer,
+            "NAME",
+            &FieldValue::StringValue("Côte d'Ivoire".to_string()),
+        )
+        .unwrap();
+        assert_eq!(ivory_coast.len(), 1);
+
+        let by_year =
+            Country::from_layer_by_attr(&mut layer, "POP_YEAR", &FieldValue::IntegerValue(2017))
+                .unwrap();
+        assert_eq!(by_year.len(), 3);
+
+        // The filter is cleared afterwards.
+        assert_eq!(Country::from_layer(&mut layer).unwrap().len(), 177);
+
+        drop(layer);
+        drop(ds);
+        for path in gdal::vsi::read_dir("/vsimem/indexed", false).unwrap() {
+            gdal::vsi::unlink_mem_file(Path::new("/vsimem/indexed").join(path)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_from_layer_dedup_by() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let countries = Country::from_layer_dedup_by(&mut layer, &["CONTINENT"]).unwrap();
+
+        // One country per continent, in order of first appearance.
+        assert_eq!(countries.len(), 8);
+        assert_eq!(countries[0].name, "Fiji");
+        assert_eq!(countries[7].name, "Antarctica");
+
+        assert!(Country::from_layer_dedup_by(&mut layer, &["NOT_A_FIELD"]).is_err());
+    }
+
+    struct LazyCountry {
+        name: String,
+        pop_est: Option<f64>,
+    }
+
+    impl FromFeature<2, TestError> for LazyCountry {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME", "POP_EST"];
+
+        fn read(
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            let [name_field, pop_est_field] = fields;
 
             Ok(Self {
-                name,
-                iso_a2,
-                iso_a3,
-                pop_est,
-                pop_year,
-                geom,
+                name: name_field.try_into_string()?,
+                pop_est: pop_est_field.try_into_real_opt()?,
             })
         }
+
+        // Only read the population of Swedes.
+        fn read_lazy(
+            _fid: Option<u64>,
+            fields: LazyFields<'_, { Self::NUM_FIELDS }>,
+            _geometry: Option<&Geometry>,
+        ) -> Result<Self, TestError> {
+            let name = fields.get(0).try_into_string()?;
+            let pop_est = match name.as_str() {
+                "Sweden" => fields.get(1).try_into_real_opt()?,
+                _ => None,
+            };
+
+            Ok(Self { name, pop_est })
+        }
+    }
+
+    #[test]
+    fn test_from_layer_lazy() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let countries = LazyCountry::from_layer_lazy(&mut layer).unwrap();
+
+        assert_eq!(countries.len(), 177);
+        assert_eq!(countries[110].name, "Sweden");
+        assert_eq!(countries[110].pop_est, Some(10285453.0));
+        assert_eq!(countries[142].pop_est, None);
+
+        let eager = LazyCountry::from_layer(&mut layer).unwrap();
+        assert_eq!(eager[142].pop_est, Some(5818553.0));
+    }
+
+    #[test]
+    fn test_from_layer_with_meta() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        let loaded = Country::from_layer_with_meta(&mut layer).unwrap();
+        assert_eq!(loaded.meta.name, "ne_110m_admin_0_countries");
+        assert!(loaded.meta.srs.is_some());
+        assert_eq!((&loaded).into_iter().count(), 177);
+
+        let (countries, meta) = loaded.into_parts();
+        assert_eq!(countries.len(), 177);
+        assert_eq!(meta.name, "ne_110m_admin_0_countries");
+
+        let loaded = Country::from_layer_with_meta(&mut layer).unwrap();
+        let names: Vec<String> = loaded.into_iter().map(|c| c.name).take(2).collect();
+        assert_eq!(names, vec!["Fiji", "Tanzania"]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_from_dataset_parallel() {
+        let path = "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp";
+        let layers = vec![DatasetLayer::Index(0); 4];
+
+        let countries = Country::from_dataset_parallel(path, &layers).unwrap();
+        assert_eq!(countries.len(), 4 * 177);
+        assert_eq!(countries[177 + 110].name, "Sweden");
+
+        let missing = [
+            DatasetLayer::Index(0),
+            DatasetLayer::Name("missing".to_string()),
+        ];
+        assert!(Country::from_dataset_parallel(path, &missing).is_err());
+    }
+
+    #[test]
+    fn test_from_dataset_with_meta() {
+        let path = "fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp";
+
+        let loaded = Country::from_dataset_with_meta(path, &DatasetLayer::Index(0)).unwrap();
+        assert_eq!(loaded.items.len(), 177);
+        assert_eq!(loaded.meta.name, "ne_110m_admin_0_countries");
+        assert_eq!(loaded.meta.source.as_deref(), Some(path));
+
+        let ds = Dataset::open(path).unwrap();
+        let loaded = Country::from_layer_with_meta(&mut ds.layer(0).unwrap()).unwrap();
+        assert_eq!(loaded.meta.source, None);
+    }
+
+    #[test]
+    fn test_from_zip() {
+        let countries = Country::from_zip(
+            "fixtures/ne_110m_admin_0_countries.zip",
+            "ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp",
+            &DatasetLayer::Index(0),
+        )
+        .unwrap();
+
+        assert_eq!(countries.len(), 177);
+        assert_eq!(countries[110].name, "Sweden");
+    }
+
+    #[test]
+    fn test_feature_style() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+
+        let layer = ds.layer(0).unwrap();
+        let feature = layer.feature(110).unwrap();
+        assert_eq!(feature_style(&feature), None);
+    }
+
+    #[test]
+    fn test_feature_native_data() {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "properties": { "name": "Gävle" },
+                "geometry": { "type": "Point", "coordinates": [17.14, 60.67] },
+                "source": "survey"
+            }]
+        }"#;
+        gdal::vsi::create_mem_file("/vsimem/native_data.geojson", geojson.as_bytes().to_vec())
+            .unwrap();
+
+        let ds = Dataset::open_ex(
+            "/vsimem/native_data.geojson",
+            gdal::DatasetOptions {
+                open_options: Some(&["NATIVE_DATA=YES"]),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let layer = ds.layer(0).unwrap();
+        let native_data = feature_native_data(&layer.feature(0).unwrap()).unwrap();
+        assert!(native_data.contains("survey"));
+
+        let ds = Dataset::open("/vsimem/native_data.geojson").unwrap();
+        let layer = ds.layer(0).unwrap();
+        assert_eq!(feature_native_data(&layer.feature(0).unwrap()), None);
+
+        gdal::vsi::unlink_mem_file("/vsimem/native_data.geojson").unwrap();
+    }
+
+    #[test]
+    fn test_read_dynamic() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let rows = read_dynamic(&mut layer, &["NAME", "POP_YEAR", "NOT_A_FIELD"]).unwrap();
+        assert_eq!(rows.len(), 177);
+        assert_eq!(rows[110][0].try_into_string().unwrap(), "Sweden");
+        assert_eq!(rows[110][1].try_into_int().unwrap(), 2019);
+        assert!(matches!(rows[110][2], FieldResult::Error(_)));
+
+        assert!(read_dynamic(&mut layer, &[]).unwrap()[0].is_empty());
+    }
+
+    #[test]
+    fn test_require_fid() {
+        assert_eq!(require_fid(Some(3)).unwrap(), 3);
+        assert!(matches!(require_fid(None), Err(GdalTraitError::NoFid)));
+
+        assert_eq!(require_fid_as::<i64>(Some(3)).unwrap(), 3);
+        assert!(matches!(
+            require_fid_as::<i64>(None),
+            Err(GdalTraitError::NoFid)
+        ));
+        assert!(matches!(
+            require_fid_as::<u8>(Some(256)),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
     }
 
     #[test]
@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use gdal::vector::{Feature, FieldValue};
+
+use crate::{FieldResult, GdalTraitError};
+
+/// Render a [`FieldValue`] for human-facing output, e.g. CSV dumps or tables.
+///
+/// Lists are rendered as `[a, b, c]` and datetimes as RFC 3339.
+pub fn format_field_value(value: &FieldValue) -> String {
+    fn list<T: Display>(values: &[T]) -> String {
+        let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        format!("[{}]", values.join(", "))
+    }
+
+    match value {
+        FieldValue::IntegerValue(v) => v.to_string(),
+        FieldValue::IntegerListValue(v) => list(v),
+        FieldValue::Integer64Value(v) => v.to_string(),
+        FieldValue::Integer64ListValue(v) => list(v),
+        FieldValue::StringValue(v) => v.clone(),
+        FieldValue::StringListValue(v) => list(v),
+        FieldValue::RealValue(v) => v.to_string(),
+        FieldValue::RealListValue(v) => list(v),
+        FieldValue::DateValue(v) => v.to_string(),
+        FieldValue::DateTimeValue(v) => v.to_rfc3339(),
+    }
+}
+
+/// Every field of a feature as `(name, value)`, formatted with [`format_field_value`] and NULLs
+/// as `"NULL"`.
+///
+/// Meant for debugging, e.g. when [`crate::FromFeature::FIELDS`] don't match the file and one
+/// needs to see what's actually in it:
+///
+/// ```ignore
+/// for (name, value) in dump_feature(&layer.feature(0).unwrap()) {
+///     println!("{name}: {value}");
+/// }
+/// ```
+pub fn dump_feature(feature: &Feature) -> Vec<(String, String)> {
+    feature
+        .fields()
+        .map(|(name, value)| {
+            let value = match value {
+                Some(value) => format_field_value(&value),
+                None => "NULL".to_string(),
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+impl FieldResult<GdalTraitError> {
+    /// Render the value with [`format_field_value`], `null_text` for NULL, or the error message.
+    ///
+    /// Meant for human-facing output only, e.g. when printing rows of an unknown schema.
+    pub fn display_or(&self, null_text: &str) -> String {
+        match self {
+            FieldResult::Some(value) => format_field_value(value),
+            FieldResult::Null => null_text.to_string(),
+            FieldResult::Error(e) => e.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdal::vector::LayerAccess;
+    use gdal::Dataset;
+
+    use super::*;
+
+    #[test]
+    fn test_dump_feature() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let layer = ds.layer(0).unwrap();
+
+        let fields = dump_feature(&layer.feature(110).unwrap());
+        assert_eq!(fields.len(), 168);
+        assert_eq!(
+            fields[0],
+            ("featurecla".to_string(), "Admin-0 country".to_string())
+        );
+        assert!(fields.contains(&("NAME".to_string(), "Sweden".to_string())));
+    }
+
+    #[test]
+    fn test_display_or() {
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::RealListValue(vec![1.5, 2.0]));
+        assert_eq!(field.display_or("NULL"), "[1.5, 2]");
+
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("Sweden".to_string()));
+        assert_eq!(field.display_or("NULL"), "Sweden");
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(field.display_or("NULL"), "NULL");
+
+        let field: FieldResult<GdalTraitError> = FieldResult::Error(GdalTraitError::NullField);
+        assert_eq!(
+            field.display_or("NULL"),
+            GdalTraitError::NullField.to_string()
+        );
+    }
+}
@@ -0,0 +1,1282 @@
+use std::ptr;
+
+use gdal::spatial_ref::{AxisMappingStrategy, CoordTransform, SpatialRef};
+use gdal::vector::{Geometry, OGRwkbGeometryType};
+use gdal_sys::OGRGeometryH;
+
+use crate::GdalTraitError;
+
+// EWKB type flags, as used by PostGIS.
+const EWKB_Z_FLAG: u32 = 0x8000_0000;
+const EWKB_M_FLAG: u32 = 0x4000_0000;
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+/// Convenience extensions for GDAL [`Geometry`].
+pub trait GeometryExt
+where
+    Self: Sized,
+{
+    /// Parse a geometry from a WKT string, e.g. a geometry column stored as text in a CSV.
+    ///
+    /// If `srs` is given, it is assigned to the resulting geometry.
+    fn from_wkt(wkt: &str, srs: Option<&SpatialRef>) -> Result<Self, GdalTraitError>;
+
+    /// Parse a geometry from WKB bytes.
+    ///
+    /// If `srs` is given, it is assigned to the resulting geometry.
+    fn from_wkb(wkb: &[u8], srs: Option<&SpatialRef>) -> Result<Self, GdalTraitError>;
+
+    /// Snap the coordinates to a grid of `grid_size`, using GEOS precision reduction.
+    ///
+    /// Useful for deterministic output and stable geometry equality. Requires GDAL >= 3.9 built
+    /// with GEOS, errors otherwise.
+    fn set_precision(&self, grid_size: f64) -> Result<Self, GdalTraitError>;
+
+    /// Owned copies of the components of a multi-geometry or geometry collection, preserving
+    /// the SRS.
+    ///
+    /// A single geometry returns itself as the only part, and an empty geometry no parts.
+    fn parts(&self) -> Result<Vec<Self>, GdalTraitError>;
+
+    /// The number of [`GeometryExt::parts`], e.g. the islands of a country, without copying them.
+    fn part_count(&self) -> usize;
+
+    /// The total number of vertices of all parts and rings, e.g. for deciding whether to simplify
+    /// before rendering.
+    ///
+    /// Vertices are counted as stored, so the closing vertex of each ring counts too.
+    fn vertex_count(&self) -> usize;
+
+    /// The bounding box as `(min_x, min_y, max_x, max_y)`, e.g. for building a spatial index.
+    ///
+    /// Errors for empty geometries, which have no extent. As [`Geometry`] has an inherent
+    /// `envelope` method, call this as `GeometryExt::envelope(&geom)`.
+    fn envelope(&self) -> Result<(f64, f64, f64, f64), GdalTraitError>;
+
+    /// The 3D bounding box as `(min_x, min_y, min_z, max_x, max_y, max_z)`.
+    ///
+    /// Errors for empty geometries, which have no extent.
+    fn envelope_3d(&self) -> Result<(f64, f64, f64, f64, f64, f64), GdalTraitError>;
+
+    /// All points as `(x, y, z, m)`, for measured (M/ZM) geometries which are lossy with `to_geo`.
+    ///
+    /// Z and M are `None` when the geometry type has no such dimension. Points of all rings and
+    /// parts are returned in order.
+    #[allow(clippy::type_complexity)]
+    fn coordinates_with_m(
+        &self,
+    ) -> Result<Vec<(f64, f64, Option<f64>, Option<f64>)>, GdalTraitError>;
+
+    /// All points as `[x, y]`, for feeding raw coordinates to e.g. a tessellator without
+    /// `geo_types`.
+    ///
+    /// Points of all rings and parts are returned in order, as one flat vector. The boundaries
+    /// of rings, lines and points within it are given by [`GeometryExt::ring_offsets`].
+    fn coordinates(&self) -> Result<Vec<[f64; 2]>, GdalTraitError>;
+
+    /// All points as `[x, y, z]`, like [`GeometryExt::coordinates`]. Z is `0` for 2D geometries.
+    fn coordinates_3d(&self) -> Result<Vec<[f64; 3]>, GdalTraitError>;
+
+    /// Delimits the rings, lines and points of [`GeometryExt::coordinates`].
+    ///
+    /// Holds the start of each, in the same order, followed by the total number of points, so
+    /// that ring `i` is `coordinates[offsets[i]..offsets[i + 1]]`. E.g. a polygon with one hole
+    /// has two rings, and a multi-point one entry per point. Empty rings are skipped. Which rings
+    /// belong to the same part can be found from [`GeometryExt::parts`].
+    fn ring_offsets(&self) -> Result<Vec<usize>, GdalTraitError>;
+
+    /// Export as PostGIS EWKB, i.e. WKB with an embedded SRID, for `ST_GeomFromEWKB`.
+    ///
+    /// If `srid` is `None`, it is derived from the authority code of the geometry's SRS. The byte
+    /// order of GDAL's WKB export is kept. Only the outer geometry carries the SRID, as PostGIS
+    /// expects.
+    fn to_ewkb(&self, srid: Option<i32>) -> Result<Vec<u8>, GdalTraitError>;
+
+    /// Reproject from `source_srs` into WGS84 (EPSG:4326), e.g. for web output.
+    ///
+    /// GDAL 3 follows the authority axis order of EPSG:4326, i.e. latitude first. The target
+    /// uses the traditional GIS order instead, so that X is the longitude and Y the latitude.
+    fn to_wgs84(&self, source_srs: &SpatialRef) -> Result<Self, GdalTraitError>;
+
+    /// Reproject in place, e.g. for large batches where the source geometry isn't needed
+    /// afterwards, saving the clone of [`Geometry::transform`]. The target SRS is assigned.
+    ///
+    /// This mutates the geometry. If a coordinate fails to transform, simple geometries are left
+    /// unchanged, while components of a collection transformed before the failing one are kept.
+    /// As [`Geometry`] has an inherent `transform_inplace`, call this as
+    /// `GeometryExt::transform_inplace(&mut geom, &transform)`.
+    fn transform_inplace(&mut self, transform: &CoordTransform) -> Result<(), GdalTraitError>;
+
+    /// Densify by inserting vertices so that no segment is longer than `max_length`, preserving
+    /// the SRS.
+    ///
+    /// Long straight segments bow when reprojected, as only their vertices are transformed, so
+    /// densify before reprojecting. `max_length` is in units of the source SRS, e.g. degrees
+    /// for geographic coordinates. Errors unless `max_length` is positive.
+    fn segmentize(&self, max_length: f64) -> Result<Self, GdalTraitError>;
+
+    /// A copy without Z and M coordinates, preserving the SRS, e.g. for 2D-only consumers of
+    /// 3D shapefiles.
+    fn to_2d(&self) -> Result<Self, GdalTraitError>;
+
+    /// Approximate curves, e.g. circular strings, compound curves and curve polygons, by linear
+    /// geometries, preserving the SRS.
+    ///
+    /// Unlike curves, the result can be converted into `geo_types`. Arcs are split into steps
+    /// of at most `max_angle_step` degrees, 0 using GDAL's default of 4 degrees. Linear
+    /// geometries are returned as they are. Errors if `max_angle_step` is negative or NaN.
+    fn linearize(&self, max_angle_step: f64) -> Result<Self, GdalTraitError>;
+
+    /// The planar area in squared SRS units, zero for non-surfaces.
+    ///
+    /// Only meaningful for projected SRSs, with geographic coordinates the result is in squared
+    /// degrees. See [`GeometryExt::geodesic_area`] for those. As [`Geometry`] has an inherent
+    /// `area` method, call this as `GeometryExt::area(&geom)`.
+    fn area(&self) -> Result<f64, GdalTraitError>;
+
+    /// The planar length in SRS units, the perimeter for surfaces and zero for points.
+    ///
+    /// Curves are measured after linearization. Like [`GeometryExt::area`], only meaningful for
+    /// projected SRSs.
+    fn length(&self) -> Result<f64, GdalTraitError>;
+
+    /// The area on the ellipsoid of the geometry's SRS, in square metres.
+    ///
+    /// Works for both geographic and projected SRSs, but errors for geometries without an SRS.
+    /// Requires GDAL >= 3.9.
+    fn geodesic_area(&self) -> Result<f64, GdalTraitError>;
+
+    /// The length on the ellipsoid of the geometry's SRS in metres, the perimeter for surfaces.
+    ///
+    /// Errors for geometries without an SRS. Requires GDAL >= 3.10.
+    fn geodesic_length(&self) -> Result<f64, GdalTraitError>;
+
+    /// The centroid as a point, preserving the SRS, e.g. as a label anchor.
+    ///
+    /// The centroid isn't necessarily on the geometry, e.g. for a ring shaped polygon, see
+    /// [`GeometryExt::point_on_surface`]. Errors for empty geometries.
+    fn centroid(&self) -> Result<Self, GdalTraitError>;
+
+    /// A point guaranteed to lie on the geometry, preserving the SRS.
+    ///
+    /// Requires GDAL built with GEOS. Errors for empty geometries.
+    fn point_on_surface(&self) -> Result<Self, GdalTraitError>;
+
+    /// The smallest convex polygon containing the geometry, preserving the SRS, e.g. as a
+    /// coarse shape for spatial prefiltering.
+    ///
+    /// Degenerate inputs give a point or line string. Requires GDAL built with GEOS. As
+    /// [`Geometry`] has an inherent `convex_hull` method, call this as
+    /// `GeometryExt::convex_hull(&geom)`.
+    fn convex_hull(&self) -> Result<Self, GdalTraitError>;
+
+    /// The minimum area rectangle containing the geometry, which may be rotated, preserving the
+    /// SRS, e.g. as a collision box.
+    ///
+    /// Computed from [`GeometryExt::convex_hull`] by rotating calipers, so it requires GEOS
+    /// likewise. Degenerate inputs give their convex hull, i.e. a point or line string. Errors
+    /// for empty geometries.
+    fn oriented_envelope(&self) -> Result<Self, GdalTraitError>;
+
+    /// The area within `distance` of the geometry, preserving the SRS, e.g. for proximity
+    /// analysis.
+    ///
+    /// `distance` is in SRS units, so buffering geographic coordinates gives a distance in
+    /// degrees. Circular arcs are approximated with `quad_segs` segments per quarter circle.
+    /// Requires GDAL built with GEOS. As [`Geometry`] has an inherent `buffer` method, call this
+    /// as `GeometryExt::buffer(&geom, distance, quad_segs)`.
+    fn buffer(&self, distance: f64, quad_segs: i32) -> Result<Self, GdalTraitError>;
+
+    /// Whether the geometries share any point, using GEOS.
+    ///
+    /// Both geometries must be in the same SRS, no reprojection takes place. Requires GDAL built
+    /// with GEOS, without which GDAL falls back to comparing envelopes; this errors instead. As
+    /// [`Geometry`] has inherent predicates, call this as `GeometryExt::intersects(&a, &b)`.
+    fn intersects(&self, other: &Geometry) -> Result<bool, GdalTraitError>;
+
+    /// Whether the geometry lies within `other`. Like [`GeometryExt::intersects`], the SRSs must
+    /// match and GEOS is required.
+    fn within(&self, other: &Geometry) -> Result<bool, GdalTraitError>;
+
+    /// Whether the geometry contains `other`. Like [`GeometryExt::intersects`], the SRSs must
+    /// match and GEOS is required.
+    fn contains(&self, other: &Geometry) -> Result<bool, GdalTraitError>;
+
+    /// Convert into a [`geo_types::Geometry`].
+    ///
+    /// Unlike [`Geometry::to_geo`], types without a `geo_types` equivalent, e.g. curves such as
+    /// CircularString, error with [`GdalTraitError::UnsupportedGeometry`] naming the type.
+    #[cfg(feature = "geo-types")]
+    fn to_geo_checked(&self) -> Result<geo_types::Geometry<f64>, GdalTraitError>;
+
+    /// Like [`GeometryExt::to_geo_checked`], but falls back to WKT for unsupported types.
+    #[cfg(feature = "geo-types")]
+    fn to_geo_or_wkt(&self) -> Result<GeoOrWkt, GdalTraitError>;
+}
+
+/// A geometry converted by [`GeometryExt::to_geo_or_wkt`].
+#[cfg(feature = "geo-types")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoOrWkt {
+    Geo(geo_types::Geometry<f64>),
+    /// The geometry type has no `geo_types` equivalent.
+    Wkt(String),
+}
+
+impl GeometryExt for Geometry {
+    fn from_wkt(wkt: &str, srs: Option<&SpatialRef>) -> Result<Self, GdalTraitError> {
+        let mut geom = Geometry::from_wkt(wkt)
+            .map_err(|e| GdalTraitError::GeometryParse(format!("Invalid WKT {wkt:?}: {e}")))?;
+
+        if let Some(srs) = srs {
+            geom.set_spatial_ref(srs.clone());
+        }
+        Ok(geom)
+    }
+
+    fn from_wkb(wkb: &[u8], srs: Option<&SpatialRef>) -> Result<Self, GdalTraitError> {
+        let mut geom = Geometry::from_wkb(wkb)
+            .map_err(|e| GdalTraitError::GeometryParse(format!("Invalid WKB: {e}")))?;
+
+        if let Some(srs) = srs {
+            geom.set_spatial_ref(srs.clone());
+        }
+        Ok(geom)
+    }
+
+    #[cfg(gdal_ge_3_9)]
+    fn set_precision(&self, grid_size: f64) -> Result<Self, GdalTraitError> {
+        let c_geom = unsafe { gdal_sys::OGR_G_SetPrecision(self.c_geometry(), grid_size, 0) };
+        owned_geometry(c_geom, "set_precision")
+    }
+
+    #[cfg(not(gdal_ge_3_9))]
+    fn set_precision(&self, _grid_size: f64) -> Result<Self, GdalTraitError> {
+        Err(GdalTraitError::GeometryOperation(
+            "set_precision requires GDAL >= 3.9".to_string(),
+        ))
+    }
+
+    fn parts(&self) -> Result<Vec<Self>, GdalTraitError> {
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !is_collection(self) {
+            return Ok(vec![self.clone()]);
+        }
+
+        let srs = self.spatial_ref();
+        Ok((0..self.geometry_count())
+            .map(|i| {
+                let mut part = (*self.get_geometry(i)).clone();
+                if let Some(srs) = &srs {
+                    part.set_spatial_ref(srs.clone());
+                }
+                part
+            })
+            .collect())
+    }
+
+    fn part_count(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else if is_collection(self) {
+            self.geometry_count()
+        } else {
+            1
+        }
+    }
+
+    fn vertex_count(&self) -> usize {
+        unsafe { count_vertices(self.c_geometry()) }
+    }
+
+    fn envelope(&self) -> Result<(f64, f64, f64, f64), GdalTraitError> {
+        if self.is_empty() {
+            return Err(GdalTraitError::GeometryOperation(
+                "Empty geometry has no envelope".to_string(),
+            ));
+        }
+
+        let env = Geometry::envelope(self);
+        Ok((env.MinX, env.MinY, env.MaxX, env.MaxY))
+    }
+
+    fn envelope_3d(&self) -> Result<(f64, f64, f64, f64, f64, f64), GdalTraitError> {
+        if self.is_empty() {
+            return Err(GdalTraitError::GeometryOperation(
+                "Empty geometry has no envelope".to_string(),
+            ));
+        }
+
+        let env = Geometry::envelope_3d(self);
+        Ok((env.MinX, env.MinY, env.MinZ, env.MaxX, env.MaxY, env.MaxZ))
+    }
+
+    fn coordinates_with_m(
+        &self,
+    ) -> Result<Vec<(f64, f64, Option<f64>, Option<f64>)>, GdalTraitError> {
+        let geometry_type = self.geometry_type();
+        let (has_z, has_m) = unsafe {
+            (
+                gdal_sys::OGR_GT_HasZ(geometry_type) != 0,
+                gdal_sys::OGR_GT_HasM(geometry_type) != 0,
+            )
+        };
+
+        let mut points = Vec::new();
+        unsafe {
+            collect_points_zm(self.c_geometry(), &mut |x, y, z, m| {
+                points.push((x, y, has_z.then_some(z), has_m.then_some(m)));
+            })
+        };
+        Ok(points)
+    }
+
+    fn coordinates(&self) -> Result<Vec<[f64; 2]>, GdalTraitError> {
+        let mut points = Vec::new();
+        unsafe { collect_points_zm(self.c_geometry(), &mut |x, y, _, _| points.push([x, y])) };
+        Ok(points)
+    }
+
+    fn coordinates_3d(&self) -> Result<Vec<[f64; 3]>, GdalTraitError> {
+        let mut points = Vec::new();
+        unsafe { collect_points_zm(self.c_geometry(), &mut |x, y, z, _| points.push([x, y, z])) };
+        Ok(points)
+    }
+
+    fn ring_offsets(&self) -> Result<Vec<usize>, GdalTraitError> {
+        let mut offsets = vec![0];
+        unsafe { collect_ring_offsets(self.c_geometry(), &mut offsets) };
+        Ok(offsets)
+    }
+
+    fn to_ewkb(&self, srid: Option<i32>) -> Result<Vec<u8>, GdalTraitError> {
+        let srid = match srid {
+            Some(srid) => srid,
+            None => self
+                .spatial_ref()
+                .and_then(|srs| srs.auth_code().ok())
+                .ok_or_else(|| {
+                    GdalTraitError::GeometryOperation(
+                        "No SRID given, and none could be derived from the SRS".to_string(),
+                    )
+                })?,
+        };
+
+        let wkb = self.wkb()?;
+        if wkb.len() < 5 {
+            return Err(GdalTraitError::GeometryOperation(
+                "GDAL exported an invalid WKB".to_string(),
+            ));
+        }
+        let (header, body) = wkb.split_at(5);
+        let type_bytes: [u8; 4] = header[1..5].try_into().expect("header is 5 bytes");
+
+        let mut ewkb = Vec::with_capacity(wkb.len() + 4);
+        ewkb.push(header[0]);
+        // 1 is little endian (NDR), 0 big endian (XDR).
+        if header[0] == 1 {
+            let ewkb_type = ewkb_type(u32::from_le_bytes(type_bytes)) | EWKB_SRID_FLAG;
+            ewkb.extend_from_slice(&ewkb_type.to_le_bytes());
+            ewkb.extend_from_slice(&srid.to_le_bytes());
+        } else {
+            let ewkb_type = ewkb_type(u32::from_be_bytes(type_bytes)) | EWKB_SRID_FLAG;
+            ewkb.extend_from_slice(&ewkb_type.to_be_bytes());
+            ewkb.extend_from_slice(&srid.to_be_bytes());
+        }
+        ewkb.extend_from_slice(body);
+
+        Ok(ewkb)
+    }
+
+    fn to_wgs84(&self, source_srs: &SpatialRef) -> Result<Self, GdalTraitError> {
+        let mut wgs84 = SpatialRef::from_epsg(4326)?;
+        wgs84.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+
+        let transform = CoordTransform::new(source_srs, &wgs84)?;
+        Ok(self.transform(&transform)?)
+    }
+
+    fn transform_inplace(&mut self, transform: &CoordTransform) -> Result<(), GdalTraitError> {
+        Ok(Geometry::transform_inplace(self, transform)?)
+    }
+
+    fn segmentize(&self, max_length: f64) -> Result<Self, GdalTraitError> {
+        if max_length.is_nan() || max_length <= 0.0 {
+            return Err(GdalTraitError::GeometryOperation(format!(
+                "segmentize needs a positive maximum segment length, got {max_length}"
+            )));
+        }
+
+        let segmentized = self.clone();
+        unsafe { gdal_sys::OGR_G_Segmentize(segmentized.c_geometry(), max_length) };
+        Ok(segmentized)
+    }
+
+    fn to_2d(&self) -> Result<Self, GdalTraitError> {
+        let flat = self.clone();
+        unsafe { gdal_sys::OGR_G_FlattenTo2D(flat.c_geometry()) };
+        Ok(flat)
+    }
+
+    fn linearize(&self, max_angle_step: f64) -> Result<Self, GdalTraitError> {
+        if max_angle_step.is_nan() || max_angle_step < 0.0 {
+            return Err(GdalTraitError::GeometryOperation(format!(
+                "linearize needs a non-negative angle step, got {max_angle_step}"
+            )));
+        }
+
+        let c_geom = unsafe {
+            gdal_sys::OGR_G_GetLinearGeometry(self.c_geometry(), max_angle_step, ptr::null_mut())
+        };
+        let mut linear = owned_geometry(c_geom, "linearize")?;
+        if let Some(srs) = self.spatial_ref() {
+            linear.set_spatial_ref(srs);
+        }
+        Ok(linear)
+    }
+
+    fn area(&self) -> Result<f64, GdalTraitError> {
+        Ok(unsafe { gdal_sys::OGR_G_Area(self.c_geometry()) })
+    }
+
+    fn length(&self) -> Result<f64, GdalTraitError> {
+        unsafe {
+            if gdal_sys::OGR_G_HasCurveGeometry(self.c_geometry(), 0) == 0 {
+                return Ok(planar_length(self.c_geometry()));
+            }
+
+            let c_linear =
+                gdal_sys::OGR_G_GetLinearGeometry(self.c_geometry(), 0.0, std::ptr::null_mut());
+            if c_linear.is_null() {
+                return Err(GdalTraitError::GeometryOperation(
+                    "Failed to linearize curve geometry".to_string(),
+                ));
+            }
+            let linear = Geometry::with_c_geometry(c_linear, true);
+            Ok(planar_length(linear.c_geometry()))
+        }
+    }
+
+    #[cfg(gdal_ge_3_9)]
+    fn geodesic_area(&self) -> Result<f64, GdalTraitError> {
+        let area = unsafe { gdal_sys::OGR_G_GeodesicArea(self.c_geometry()) };
+        // Negative on failure, e.g. without an SRS.
+        if area < 0.0 {
+            return Err(GdalTraitError::GeometryOperation(
+                "geodesic_area failed, does the geometry have an SRS?".to_string(),
+            ));
+        }
+        Ok(area)
+    }
+
+    #[cfg(not(gdal_ge_3_9))]
+    fn geodesic_area(&self) -> Result<f64, GdalTraitError> {
+        Err(GdalTraitError::GeometryOperation(
+            "geodesic_area requires GDAL >= 3.9".to_string(),
+        ))
+    }
+
+    #[cfg(gdal_ge_3_10)]
+    fn geodesic_length(&self) -> Result<f64, GdalTraitError> {
+        let length = unsafe { gdal_sys::OGR_G_GeodesicLength(self.c_geometry()) };
+        if length < 0.0 {
+            return Err(GdalTraitError::GeometryOperation(
+                "geodesic_length failed, does the geometry have an SRS?".to_string(),
+            ));
+        }
+        Ok(length)
+    }
+
+    #[cfg(not(gdal_ge_3_10))]
+    fn geodesic_length(&self) -> Result<f64, GdalTraitError> {
+        Err(GdalTraitError::GeometryOperation(
+            "geodesic_length requires GDAL >= 3.10".to_string(),
+        ))
+    }
+
+    fn centroid(&self) -> Result<Self, GdalTraitError> {
+        if self.is_empty() {
+            return Err(GdalTraitError::GeometryOperation(
+                "Empty geometry has no centroid".to_string(),
+            ));
+        }
+
+        let mut centroid = Geometry::empty(OGRwkbGeometryType::wkbPoint)?;
+        let res = unsafe { gdal_sys::OGR_G_Centroid(self.c_geometry(), centroid.c_geometry()) };
+        if res != gdal_sys::OGRErr::OGRERR_NONE {
+            return Err(GdalTraitError::GeometryOperation(format!(
+                "centroid failed with OGR error {res}"
+            )));
+        }
+
+        if let Some(srs) = self.spatial_ref() {
+            centroid.set_spatial_ref(srs);
+        }
+        Ok(centroid)
+    }
+
+    fn point_on_surface(&self) -> Result<Self, GdalTraitError> {
+        if self.is_empty() {
+            return Err(GdalTraitError::GeometryOperation(
+                "Empty geometry has no point on surface".to_string(),
+            ));
+        }
+
+        let c_geom = unsafe { gdal_sys::OGR_G_PointOnSurface(self.c_geometry()) };
+        let mut point = owned_geometry(c_geom, "point_on_surface")?;
+        if let Some(srs) = self.spatial_ref() {
+            point.set_spatial_ref(srs);
+        }
+        Ok(point)
+    }
+
+    fn convex_hull(&self) -> Result<Self, GdalTraitError> {
+        let c_geom = unsafe { gdal_sys::OGR_G_ConvexHull(self.c_geometry()) };
+        let mut hull = owned_geometry(c_geom, "convex_hull")?;
+        if let Some(srs) = self.spatial_ref() {
+            hull.set_spatial_ref(srs);
+        }
+        Ok(hull)
+    }
+
+    fn oriented_envelope(&self) -> Result<Self, GdalTraitError> {
+        if self.is_empty() {
+            return Err(GdalTraitError::GeometryOperation(
+                "Empty geometry has no oriented envelope".to_string(),
+            ));
+        }
+
+        let hull = GeometryExt::convex_hull(self)?;
+        if unsafe { gdal_sys::OGR_GT_Flatten(hull.geometry_type()) }
+            != OGRwkbGeometryType::wkbPolygon
+        {
+            return Ok(hull);
+        }
+
+        let mut points = hull.coordinates()?;
+        points.pop(); // The closing point.
+        let corners = min_area_rectangle(&points);
+
+        let wkt = format!(
+            "POLYGON (({}))",
+            corners
+                .iter()
+                .chain(&corners[..1])
+                .map(|[x, y]| format!("{x} {y}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        GeometryExt::from_wkt(&wkt, self.spatial_ref().as_ref())
+    }
+
+    fn buffer(&self, distance: f64, quad_segs: i32) -> Result<Self, GdalTraitError> {
+        if quad_segs < 1 {
+            return Err(GdalTraitError::GeometryOperation(format!(
+                "buffer needs at least one segment per quarter circle, got {quad_segs}"
+            )));
+        }
+
+        let c_geom = unsafe { gdal_sys::OGR_G_Buffer(self.c_geometry(), distance, quad_segs) };
+        let mut buffered = owned_geometry(c_geom, "buffer")?;
+        if let Some(srs) = self.spatial_ref() {
+            buffered.set_spatial_ref(srs);
+        }
+        Ok(buffered)
+    }
+
+    fn intersects(&self, other: &Geometry) -> Result<bool, GdalTraitError> {
+        require_geos("intersects")?;
+        Ok(unsafe { gdal_sys::OGR_G_Intersects(self.c_geometry(), other.c_geometry()) } != 0)
+    }
+
+    fn within(&self, other: &Geometry) -> Result<bool, GdalTraitError> {
+        require_geos("within")?;
+        Ok(unsafe { gdal_sys::OGR_G_Within(self.c_geometry(), other.c_geometry()) } != 0)
+    }
+
+    fn contains(&self, other: &Geometry) -> Result<bool, GdalTraitError> {
+        require_geos("contains")?;
+        Ok(unsafe { gdal_sys::OGR_G_Contains(self.c_geometry(), other.c_geometry()) } != 0)
+    }
+
+    #[cfg(feature = "geo-types")]
+    fn to_geo_checked(&self) -> Result<geo_types::Geometry<f64>, GdalTraitError> {
+        if !is_geo_compatible(self.geometry_type()) {
+            return Err(GdalTraitError::UnsupportedGeometry(geometry_type_name(
+                self.geometry_type(),
+            )));
+        }
+
+        // Collections can still contain unsupported parts.
+        self.to_geo().map_err(|e| match e {
+            gdal::errors::GdalError::UnsupportedGdalGeometryType(geometry_type) => {
+                GdalTraitError::UnsupportedGeometry(geometry_type_name(geometry_type))
+            }
+            e => e.into(),
+        })
+    }
+
+    #[cfg(feature = "geo-types")]
+    fn to_geo_or_wkt(&self) -> Result<GeoOrWkt, GdalTraitError> {
+        match self.to_geo_checked() {
+            Ok(geom) => Ok(GeoOrWkt::Geo(geom)),
+            Err(GdalTraitError::UnsupportedGeometry(_)) => Ok(GeoOrWkt::Wkt(self.wkt()?)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Whether a geometry type has a `geo_types` equivalent, ignoring Z and M.
+#[cfg(feature = "geo-types")]
+fn is_geo_compatible(geometry_type: OGRwkbGeometryType::Type) -> bool {
+    matches!(
+        unsafe { gdal_sys::OGR_GT_Flatten(geometry_type) },
+        OGRwkbGeometryType::wkbPoint
+            | OGRwkbGeometryType::wkbLineString
+            | OGRwkbGeometryType::wkbLinearRing
+            | OGRwkbGeometryType::wkbPolygon
+            | OGRwkbGeometryType::wkbMultiPoint
+            | OGRwkbGeometryType::wkbMultiLineString
+            | OGRwkbGeometryType::wkbMultiPolygon
+            | OGRwkbGeometryType::wkbGeometryCollection
+    )
+}
+
+/// Human readable name of a geometry type, e.g. "Circular String".
+#[cfg(feature = "geo-types")]
+fn geometry_type_name(geometry_type: OGRwkbGeometryType::Type) -> String {
+    unsafe { std::ffi::CStr::from_ptr(gdal_sys::OGRGeometryTypeToName(geometry_type)) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Convert a WKB geometry type code into its EWKB equivalent, mapping ISO dimension offsets
+/// (e.g. 1001 for Point Z) into flags.
+fn ewkb_type(code: u32) -> u32 {
+    // Old OGC style export, which already uses the Z flag.
+    if code & (EWKB_Z_FLAG | EWKB_M_FLAG) != 0 {
+        return code;
+    }
+
+    code % 1000
+        | match code / 1000 {
+            1 => EWKB_Z_FLAG,
+            2 => EWKB_M_FLAG,
+            3 => EWKB_Z_FLAG | EWKB_M_FLAG,
+            _ => 0,
+        }
+}
+
+/// Call `f` with the `(x, y, z, m)` of every point of a geometry, recursing into rings and
+/// parts.
+unsafe fn collect_points_zm(c_geom: OGRGeometryH, f: &mut impl FnMut(f64, f64, f64, f64)) {
+    let geometry_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if geometry_count > 0 {
+        for i in 0..geometry_count {
+            collect_points_zm(gdal_sys::OGR_G_GetGeometryRef(c_geom, i), f);
+        }
+        return;
+    }
+
+    for i in 0..gdal_sys::OGR_G_GetPointCount(c_geom) {
+        let (mut x, mut y, mut z, mut m) = (0.0, 0.0, 0.0, 0.0);
+        gdal_sys::OGR_G_GetPointZM(c_geom, i, &mut x, &mut y, &mut z, &mut m);
+        f(x, y, z, m);
+    }
+}
+
+/// The number of points of a geometry, summed over all of its components.
+unsafe fn count_vertices(c_geom: OGRGeometryH) -> usize {
+    let geometry_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if geometry_count > 0 {
+        return (0..geometry_count)
+            .map(|i| count_vertices(gdal_sys::OGR_G_GetGeometryRef(c_geom, i)))
+            .sum();
+    }
+
+    gdal_sys::OGR_G_GetPointCount(c_geom) as usize
+}
+
+/// Push the end offset of every non-empty ring, line or point of a geometry onto `offsets`,
+/// which starts with the offset of the first one, in the order of [`collect_points_zm`].
+unsafe fn collect_ring_offsets(c_geom: OGRGeometryH, offsets: &mut Vec<usize>) {
+    let geometry_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if geometry_count > 0 {
+        for i in 0..geometry_count {
+            collect_ring_offsets(gdal_sys::OGR_G_GetGeometryRef(c_geom, i), offsets);
+        }
+        return;
+    }
+
+    let point_count = gdal_sys::OGR_G_GetPointCount(c_geom) as usize;
+    if point_count > 0 {
+        let start = offsets.last().copied().unwrap_or(0);
+        offsets.push(start + point_count);
+    }
+}
+
+/// Sum of the 2D segment lengths of a linear geometry, recursing into rings and parts.
+unsafe fn planar_length(c_geom: OGRGeometryH) -> f64 {
+    let geometry_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if geometry_count > 0 {
+        return (0..geometry_count)
+            .map(|i| planar_length(gdal_sys::OGR_G_GetGeometryRef(c_geom, i)))
+            .sum();
+    }
+
+    let point = |i| {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        gdal_sys::OGR_G_GetPoint(c_geom, i, &mut x, &mut y, &mut z);
+        (x, y)
+    };
+    (1..gdal_sys::OGR_G_GetPointCount(c_geom))
+        .map(|i| {
+            let ((x0, y0), (x1, y1)) = (point(i - 1), point(i));
+            (x1 - x0).hypot(y1 - y0)
+        })
+        .sum()
+}
+
+/// Whether the geometry is a multi-geometry or geometry collection.
+fn is_collection(geom: &Geometry) -> bool {
+    unsafe {
+        gdal_sys::OGR_GT_IsSubClassOf(
+            gdal_sys::OGR_GT_Flatten(geom.geometry_type()),
+            OGRwkbGeometryType::wkbGeometryCollection,
+        ) != 0
+    }
+}
+
+/// Error if GDAL is built without GEOS, naming the operation `op`.
+///
+/// GEOS availability can only be queried from GDAL 3.4, before which it's assumed.
+#[cfg(gdal_ge_3_4)]
+fn require_geos(op: &str) -> Result<(), GdalTraitError> {
+    if unsafe {
+        gdal_sys::OGRGetGEOSVersion(
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    } != 0
+    {
+        return Ok(());
+    }
+    Err(GdalTraitError::GeometryOperation(format!(
+        "{op} requires GDAL built with GEOS"
+    )))
+}
+
+#[cfg(not(gdal_ge_3_4))]
+fn require_geos(_op: &str) -> Result<(), GdalTraitError> {
+    Ok(())
+}
+
+/// The corners of the minimum area rectangle around the points of a convex polygon.
+///
+/// One side of the rectangle lies on an edge of the polygon, so each edge direction is tried.
+fn min_area_rectangle(points: &[[f64; 2]]) -> [[f64; 2]; 4] {
+    let mut best = (f64::INFINITY, [[0.0; 2]; 4]);
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        let length = (b[0] - a[0]).hypot(b[1] - a[1]);
+        if length == 0.0 {
+            continue;
+        }
+        // Unit vectors along and across the edge.
+        let u = [(b[0] - a[0]) / length, (b[1] - a[1]) / length];
+        let v = [-u[1], u[0]];
+
+        let (mut min_u, mut max_u, mut min_v, mut max_v) = (
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        );
+        for p in points {
+            let (pu, pv) = (p[0] * u[0] + p[1] * u[1], p[0] * v[0] + p[1] * v[1]);
+            (min_u, max_u) = (min_u.min(pu), max_u.max(pu));
+            (min_v, max_v) = (min_v.min(pv), max_v.max(pv));
+        }
+
+        let area = (max_u - min_u) * (max_v - min_v);
+        if area < best.0 {
+            let corner = |pu: f64, pv: f64| [pu * u[0] + pv * v[0], pu * u[1] + pv * v[1]];
+            best = (
+                area,
+                [
+                    corner(min_u, min_v),
+                    corner(max_u, min_v),
+                    corner(max_u, max_v),
+                    corner(min_u, max_v),
+                ],
+            );
+        }
+    }
+    best.1
+}
+
+/// Take ownership of a geometry returned by GDAL, with `op` naming the operation in the error
+/// if it's NULL.
+fn owned_geometry(c_geom: OGRGeometryH, op: &str) -> Result<Geometry, GdalTraitError> {
+    if c_geom.is_null() {
+        return Err(GdalTraitError::GeometryOperation(format!(
+            "{op} returned no geometry, is GDAL built with GEOS?"
+        )));
+    }
+    Ok(unsafe { Geometry::with_c_geometry(c_geom, true) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_wkt() {
+        let srs = SpatialRef::from_epsg(4326).unwrap();
+        let geom: Geometry = GeometryExt::from_wkt("POINT (1 2)", Some(&srs)).unwrap();
+
+        assert_eq!(geom.get_point(0), (1.0, 2.0, 0.0));
+        assert!(geom.spatial_ref().is_some());
+
+        let wkb = geom.wkb().unwrap();
+        let geom: Geometry = GeometryExt::from_wkb(&wkb, None).unwrap();
+        assert_eq!(geom.get_point(0), (1.0, 2.0, 0.0));
+
+        assert!(matches!(
+            <Geometry as GeometryExt>::from_wkt("POINT (1", None),
+            Err(GdalTraitError::GeometryParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_parts() {
+        let srs = SpatialRef::from_epsg(4326).unwrap();
+        let geom: Geometry = GeometryExt::from_wkt(
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((2 2, 3 2, 3 3, 2 2)))",
+            Some(&srs),
+        )
+        .unwrap();
+
+        let parts = geom.parts().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(geom.part_count(), 2);
+        assert_eq!(parts[1].wkt().unwrap(), "POLYGON ((2 2,3 2,3 3,2 2))");
+        assert!(parts.iter().all(|part| part.spatial_ref().is_some()));
+
+        let polygon = Geometry::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 0))").unwrap();
+        assert_eq!(polygon.parts().unwrap().len(), 1);
+        assert_eq!(polygon.part_count(), 1);
+
+        let empty = Geometry::from_wkt("MULTIPOLYGON EMPTY").unwrap();
+        assert!(empty.parts().unwrap().is_empty());
+        assert_eq!(empty.part_count(), 0);
+    }
+
+    #[test]
+    fn test_envelope() {
+        let geom = Geometry::from_wkt("LINESTRING (0 1 2, 3 -4 5)").unwrap();
+
+        assert_eq!(GeometryExt::envelope(&geom).unwrap(), (0.0, -4.0, 3.0, 1.0));
+        assert_eq!(
+            GeometryExt::envelope_3d(&geom).unwrap(),
+            (0.0, -4.0, 2.0, 3.0, 1.0, 5.0)
+        );
+
+        let empty = Geometry::from_wkt("POINT EMPTY").unwrap();
+        assert!(GeometryExt::envelope(&empty).is_err());
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let geom = Geometry::from_wkt(
+            "MULTIPOLYGON (((0 0, 4 0, 4 4, 0 0), (1 1, 2 1, 2 2, 1 1)), ((5 5, 6 5, 6 6, 5 5)))",
+        )
+        .unwrap();
+
+        let coordinates = geom.coordinates().unwrap();
+        assert_eq!(coordinates.len(), 12);
+        assert_eq!(coordinates[4], [1.0, 1.0]);
+
+        let offsets = geom.ring_offsets().unwrap();
+        assert_eq!(offsets, vec![0, 4, 8, 12]);
+        assert_eq!(
+            coordinates[offsets[2]..offsets[3]],
+            [[5.0, 5.0], [6.0, 5.0], [6.0, 6.0], [5.0, 5.0]]
+        );
+
+        let line = Geometry::from_wkt("LINESTRING Z (0 1 2, 3 4 5)").unwrap();
+        assert_eq!(
+            line.coordinates_3d().unwrap(),
+            vec![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]
+        );
+        assert_eq!(line.ring_offsets().unwrap(), vec![0, 2]);
+
+        let points = Geometry::from_wkt("MULTIPOINT (0 0, 1 1)").unwrap();
+        assert_eq!(points.ring_offsets().unwrap(), vec![0, 1, 2]);
+
+        let empty = Geometry::from_wkt("POLYGON EMPTY").unwrap();
+        assert!(empty.coordinates().unwrap().is_empty());
+        assert_eq!(empty.ring_offsets().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_coordinates_with_m() {
+        let geom = Geometry::from_wkt("LINESTRING ZM (0 1 2 3, 4 5 6 7)").unwrap();
+        assert_eq!(
+            geom.coordinates_with_m().unwrap(),
+            vec![
+                (0.0, 1.0, Some(2.0), Some(3.0)),
+                (4.0, 5.0, Some(6.0), Some(7.0))
+            ]
+        );
+
+        let geom = Geometry::from_wkt("MULTILINESTRING M ((0 1 3), (4 5 7))").unwrap();
+        assert_eq!(
+            geom.coordinates_with_m().unwrap(),
+            vec![(0.0, 1.0, None, Some(3.0)), (4.0, 5.0, None, Some(7.0))]
+        );
+
+        let geom = Geometry::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 0))").unwrap();
+        let points = geom.coordinates_with_m().unwrap();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[1], (1.0, 0.0, None, None));
+    }
+
+    /// Split EWKB into its SRID and plain WKB.
+    fn from_ewkb(ewkb: &[u8]) -> (i32, Vec<u8>) {
+        let le = ewkb[0] == 1;
+        let read_u32 = |bytes: &[u8]| {
+            let bytes: [u8; 4] = bytes.try_into().unwrap();
+            if le {
+                u32::from_le_bytes(bytes)
+            } else {
+                u32::from_be_bytes(bytes)
+            }
+        };
+
+        let ewkb_type = read_u32(&ewkb[1..5]);
+        assert_ne!(ewkb_type & EWKB_SRID_FLAG, 0);
+        let srid = read_u32(&ewkb[5..9]) as i32;
+
+        let wkb_type = ewkb_type & !EWKB_SRID_FLAG;
+        let type_bytes = if le {
+            wkb_type.to_le_bytes()
+        } else {
+            wkb_type.to_be_bytes()
+        };
+
+        let mut wkb = vec![ewkb[0]];
+        wkb.extend_from_slice(&type_bytes);
+        wkb.extend_from_slice(&ewkb[9..]);
+        (srid, wkb)
+    }
+
+    #[test]
+    fn test_to_ewkb() {
+        let srs = SpatialRef::from_epsg(3006).unwrap();
+        let geom: Geometry = GeometryExt::from_wkt("POINT Z (1 2 3)", Some(&srs)).unwrap();
+
+        let ewkb = geom.to_ewkb(None).unwrap();
+        let (srid, wkb) = from_ewkb(&ewkb);
+        assert_eq!(srid, 3006);
+        assert_eq!(
+            Geometry::from_wkb(&wkb).unwrap().wkt().unwrap(),
+            geom.wkt().unwrap()
+        );
+
+        let geom = Geometry::from_wkt("POINT (1 2)").unwrap();
+        let ewkb = geom.to_ewkb(Some(4326)).unwrap();
+        assert_eq!(from_ewkb(&ewkb).0, 4326);
+        assert!(geom.to_ewkb(None).is_err());
+    }
+
+    #[test]
+    fn test_to_wgs84() {
+        let sweref = SpatialRef::from_epsg(3006).unwrap();
+        let geom = Geometry::from_wkt("POINT (500000 6500000)").unwrap();
+
+        let wgs84 = geom.to_wgs84(&sweref).unwrap();
+        let (lon, lat, _) = wgs84.get_point(0);
+        // The central meridian of SWEREF 99 TM is 15 degrees east.
+        assert!((lon - 15.0).abs() < 1e-6);
+        assert!((58.0..59.0).contains(&lat));
+    }
+
+    #[test]
+    fn test_vertex_count() {
+        let count = |wkt| Geometry::from_wkt(wkt).unwrap().vertex_count();
+
+        assert_eq!(count("POINT (1 2)"), 1);
+        assert_eq!(count("POINT EMPTY"), 0);
+        assert_eq!(count("LINESTRING (0 0, 1 1, 2 0)"), 3);
+        assert_eq!(
+            count("POLYGON ((0 0, 4 0, 4 4, 0 0), (1 1, 2 1, 2 2, 1 1))"),
+            8
+        );
+        assert_eq!(
+            count(
+                "GEOMETRYCOLLECTION (POINT (0 0), \
+                 MULTILINESTRING ((0 0, 1 1), (2 2, 3 3, 4 4)), \
+                 GEOMETRYCOLLECTION (POINT (5 5)))"
+            ),
+            7
+        );
+        assert_eq!(
+            count("COMPOUNDCURVE (CIRCULARSTRING (0 0, 1 1, 2 0), (2 0, 3 0))"),
+            5
+        );
+    }
+
+    #[test]
+    fn test_to_2d() {
+        let srs = SpatialRef::from_epsg(3006).unwrap();
+        let geom: Geometry =
+            GeometryExt::from_wkt("LINESTRING ZM (0 1 2 3, 4 5 6 7)", Some(&srs)).unwrap();
+
+        let flat = geom.to_2d().unwrap();
+        assert_eq!(flat.wkt().unwrap(), "LINESTRING (0 1,4 5)");
+        assert_eq!(flat.geometry_type(), OGRwkbGeometryType::wkbLineString);
+        assert_eq!(flat.spatial_ref().unwrap().auth_code().unwrap(), 3006);
+        // The original keeps its coordinates.
+        assert_eq!(geom.geometry_type(), OGRwkbGeometryType::wkbLineStringZM);
+    }
+
+    #[test]
+    fn test_linearize() {
+        let srs = SpatialRef::from_epsg(3006).unwrap();
+        let arc: Geometry =
+            GeometryExt::from_wkt("CIRCULARSTRING (0 0, 1 1, 2 0)", Some(&srs)).unwrap();
+
+        let line = arc.linearize(0.0).unwrap();
+        assert_eq!(line.geometry_type(), OGRwkbGeometryType::wkbLineString);
+        assert!(line.point_count() > 3);
+        assert_eq!(line.get_point(0), (0.0, 0.0, 0.0));
+        assert_eq!(line.spatial_ref().unwrap().auth_code().unwrap(), 3006);
+        // Finer steps give more vertices.
+        assert!(arc.linearize(1.0).unwrap().point_count() > line.point_count());
+
+        let curve_polygon =
+            Geometry::from_wkt("CURVEPOLYGON (CIRCULARSTRING (0 0, 2 0, 0 0))").unwrap();
+        assert_eq!(
+            curve_polygon.linearize(0.0).unwrap().geometry_type(),
+            OGRwkbGeometryType::wkbPolygon
+        );
+
+        let point = Geometry::from_wkt("POINT (1 2)").unwrap();
+        assert_eq!(point.linearize(0.0).unwrap().wkt().unwrap(), "POINT (1 2)");
+        assert!(arc.linearize(-1.0).is_err());
+
+        #[cfg(feature = "geo-types")]
+        assert!(line.to_geo_checked().is_ok());
+    }
+
+    #[test]
+    fn test_transform_inplace() {
+        let mut wgs84 = SpatialRef::from_epsg(4326).unwrap();
+        wgs84.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        let mercator = SpatialRef::from_epsg(3857).unwrap();
+        let transform = CoordTransform::new(&wgs84, &mercator).unwrap();
+
+        let mut geom = Geometry::from_wkt("POINT (15 0)").unwrap();
+        GeometryExt::transform_inplace(&mut geom, &transform).unwrap();
+        let (x, y, _) = geom.get_point(0);
+        assert!((x - 1_669_792.36).abs() < 0.01);
+        assert!(y.abs() < 1e-6);
+        assert_eq!(geom.spatial_ref().unwrap().auth_code().unwrap(), 3857);
+
+        // The poles are at infinity in Web Mercator.
+        let mut line = Geometry::from_wkt("LINESTRING (15 0, 15 90)").unwrap();
+        assert!(GeometryExt::transform_inplace(&mut line, &transform).is_err());
+        assert_eq!(line.get_point(0), (15.0, 0.0, 0.0));
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn test_to_geo_checked() {
+        let geom = Geometry::from_wkt("POINT (1 2)").unwrap();
+        assert_eq!(
+            geom.to_geo_checked().unwrap(),
+            geo_types::Geometry::Point(geo_types::Point::new(1.0, 2.0))
+        );
+
+        let curve = Geometry::from_wkt("CIRCULARSTRING (0 0, 1 1, 2 0)").unwrap();
+        let Err(GdalTraitError::UnsupportedGeometry(name)) = curve.to_geo_checked() else {
+            panic!("Expected an unsupported geometry error");
+        };
+        assert_eq!(name, "Circular String");
+
+        assert!(matches!(
+            curve.to_geo_or_wkt().unwrap(),
+            GeoOrWkt::Wkt(wkt) if wkt.starts_with("CIRCULARSTRING")
+        ));
+        assert!(matches!(geom.to_geo_or_wkt().unwrap(), GeoOrWkt::Geo(_)));
+    }
+
+    #[test]
+    fn test_ewkb_type() {
+        assert_eq!(ewkb_type(1), 1);
+        assert_eq!(ewkb_type(1001), 1 | EWKB_Z_FLAG);
+        assert_eq!(ewkb_type(2003), 3 | EWKB_M_FLAG);
+        assert_eq!(ewkb_type(3006), 6 | EWKB_Z_FLAG | EWKB_M_FLAG);
+        assert_eq!(ewkb_type(0x8000_0002), 0x8000_0002);
+    }
+
+    #[test]
+    fn test_centroid() {
+        let srs = SpatialRef::from_epsg(3006).unwrap();
+        let square: Geometry =
+            GeometryExt::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))", Some(&srs)).unwrap();
+
+        let centroid = GeometryExt::centroid(&square).unwrap();
+        assert_eq!(centroid.get_point(0), (5.0, 5.0, 0.0));
+        assert_eq!(centroid.spatial_ref().unwrap().auth_code().unwrap(), 3006);
+
+        // The centroid of a U shape lies outside of it.
+        let u_shape =
+            Geometry::from_wkt("POLYGON ((0 0, 10 0, 10 10, 8 10, 8 2, 2 2, 2 10, 0 10, 0 0))")
+                .unwrap();
+        let point = u_shape.point_on_surface().unwrap();
+        assert!(GeometryExt::contains(&u_shape, &point).unwrap());
+        let centroid = GeometryExt::centroid(&u_shape).unwrap();
+        assert!(!GeometryExt::contains(&u_shape, &centroid).unwrap());
+
+        let empty = Geometry::empty(OGRwkbGeometryType::wkbPolygon).unwrap();
+        assert!(GeometryExt::centroid(&empty).is_err());
+        assert!(empty.point_on_surface().is_err());
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let srs = SpatialRef::from_epsg(3006).unwrap();
+        let u_shape: Geometry = GeometryExt::from_wkt(
+            "POLYGON ((0 0, 10 0, 10 10, 8 10, 8 2, 2 2, 2 10, 0 10, 0 0))",
+            Some(&srs),
+        )
+        .unwrap();
+
+        let hull = GeometryExt::convex_hull(&u_shape).unwrap();
+        assert_eq!(GeometryExt::area(&hull).unwrap(), 100.0);
+        assert_eq!(hull.spatial_ref().unwrap().auth_code().unwrap(), 3006);
+
+        // A square rotated by 45 degrees, whose axis-aligned envelope is twice as large.
+        let diamond: Geometry =
+            GeometryExt::from_wkt("POLYGON ((0 -1, 1 0, 0 1, -1 0, 0 -1))", Some(&srs)).unwrap();
+        let oriented = diamond.oriented_envelope().unwrap();
+        assert!((GeometryExt::area(&oriented).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(oriented.spatial_ref().unwrap().auth_code().unwrap(), 3006);
+
+        let line = Geometry::from_wkt("LINESTRING (0 0, 1 1, 2 2)").unwrap();
+        assert_eq!(
+            line.oriented_envelope().unwrap().geometry_type(),
+            OGRwkbGeometryType::wkbLineString
+        );
+
+        let empty = Geometry::empty(OGRwkbGeometryType::wkbPolygon).unwrap();
+        assert!(empty.oriented_envelope().is_err());
+    }
+
+    #[test]
+    fn test_segmentize() {
+        let srs = SpatialRef::from_epsg(4326).unwrap();
+        let line: Geometry = GeometryExt::from_wkt("LINESTRING (0 0, 10 0)", Some(&srs)).unwrap();
+
+        let dense = line.segmentize(1.0).unwrap();
+        assert_eq!(dense.point_count(), 11);
+        assert_eq!(dense.get_point(5), (5.0, 0.0, 0.0));
+        assert_eq!(dense.spatial_ref().unwrap().auth_code().unwrap(), 4326);
+        // The original is left untouched.
+        assert_eq!(line.point_count(), 2);
+
+        assert!(line.segmentize(0.0).is_err());
+        assert!(line.segmentize(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_buffer() {
+        let srs = SpatialRef::from_epsg(3006).unwrap();
+        let point: Geometry = GeometryExt::from_wkt("POINT (0 0)", Some(&srs)).unwrap();
+
+        let circle = GeometryExt::buffer(&point, 10.0, 8).unwrap();
+        assert_eq!(circle.geometry_type(), OGRwkbGeometryType::wkbPolygon);
+        assert_eq!(circle.spatial_ref().unwrap().auth_code().unwrap(), 3006);
+        // An inscribed polygon, slightly smaller than the circle.
+        let area = GeometryExt::area(&circle).unwrap();
+        assert!(area < std::f64::consts::PI * 100.0 && area > 300.0);
+
+        assert!(GeometryExt::buffer(&point, 10.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_predicates() {
+        let square = Geometry::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))").unwrap();
+        let inside = Geometry::from_wkt("POINT (5 5)").unwrap();
+        // Within the envelope of the triangle, but not the triangle itself.
+        let triangle = Geometry::from_wkt("POLYGON ((0 0, 10 0, 0 10, 0 0))").unwrap();
+        let corner = Geometry::from_wkt("POINT (9 9)").unwrap();
+
+        assert!(GeometryExt::intersects(&square, &inside).unwrap());
+        assert!(GeometryExt::within(&inside, &square).unwrap());
+        assert!(GeometryExt::contains(&square, &inside).unwrap());
+        assert!(!GeometryExt::contains(&inside, &square).unwrap());
+
+        assert!(!GeometryExt::intersects(&triangle, &corner).unwrap());
+        assert!(!GeometryExt::within(&corner, &triangle).unwrap());
+    }
+
+    #[test]
+    fn test_area_length() {
+        let square = Geometry::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))").unwrap();
+        assert_eq!(GeometryExt::area(&square).unwrap(), 100.0);
+        assert_eq!(GeometryExt::length(&square).unwrap(), 40.0);
+
+        let line = Geometry::from_wkt("MULTILINESTRING ((0 0, 3 4), (0 0, 0 1))").unwrap();
+        assert_eq!(GeometryExt::area(&line).unwrap(), 0.0);
+        assert_eq!(GeometryExt::length(&line).unwrap(), 6.0);
+
+        let point = Geometry::from_wkt("POINT (1 2)").unwrap();
+        assert_eq!(GeometryExt::length(&point).unwrap(), 0.0);
+
+        // Half circle of radius 1.
+        let curve = Geometry::from_wkt("CIRCULARSTRING (-1 0, 0 1, 1 0)").unwrap();
+        let length = GeometryExt::length(&curve).unwrap();
+        assert!((length - std::f64::consts::PI).abs() < 1e-2);
+    }
+
+    #[cfg(gdal_ge_3_9)]
+    #[test]
+    fn test_geodesic_area() {
+        let wgs84 = SpatialRef::from_epsg(4326).unwrap();
+        let square: Geometry =
+            GeometryExt::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))", Some(&wgs84)).unwrap();
+
+        // About 12 300 km² at the equator.
+        let area = square.geodesic_area().unwrap();
+        assert!((1.2e10..1.25e10).contains(&area));
+
+        let no_srs = Geometry::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))").unwrap();
+        assert!(no_srs.geodesic_area().is_err());
+    }
+
+    #[cfg(gdal_ge_3_10)]
+    #[test]
+    fn test_geodesic_length() {
+        let wgs84 = SpatialRef::from_epsg(4326).unwrap();
+        let line: Geometry = GeometryExt::from_wkt("LINESTRING (0 0, 0 1)", Some(&wgs84)).unwrap();
+
+        // One degree, of either latitude or longitude depending on the axis order.
+        let length = line.geodesic_length().unwrap();
+        assert!((110_000.0..112_000.0).contains(&length));
+    }
+
+    #[cfg(gdal_ge_3_9)]
+    #[test]
+    fn test_set_precision() {
+        let geom = Geometry::from_wkt("POINT (1.26 2.74)").unwrap();
+        let snapped = geom.set_precision(0.5).unwrap();
+
+        assert_eq!(snapped.get_point(0), (1.5, 2.5, 0.0));
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::{FieldResult, GdalTraitError};
+
+/// Deduplicates repeated strings, e.g. categories of a land-use layer, into shared `Arc<str>`s.
+///
+/// Used through [`FieldResult::try_into_interned`], typically in
+/// [`crate::FromFeature::read_interned`].
+#[derive(Debug, Default, Clone)]
+pub struct StringInterner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl StringInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared copy of `s`, allocated only the first time `s` is seen.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(s) {
+            return interned.clone();
+        }
+
+        let interned: Arc<str> = Arc::from(s);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// Number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl FieldResult<GdalTraitError> {
+    /// Attempt to convert a string `FieldResult` into an `Arc<str>` shared through `interner`.
+    pub fn try_into_interned(
+        &self,
+        interner: &mut StringInterner,
+    ) -> Result<Arc<str>, GdalTraitError> {
+        Ok(interner.intern(self.as_str()?))
+    }
+
+    /// Attempt to convert a string `FieldResult` into an Option<`Arc<str>`> shared through
+    /// `interner`.
+    pub fn try_into_interned_opt(
+        &self,
+        interner: &mut StringInterner,
+    ) -> Result<Option<Arc<str>>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_interned(interner).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdal::vector::FieldValue;
+
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = StringInterner::new();
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("Forest".to_string()));
+
+        let a = field.try_into_interned(&mut interner).unwrap();
+        let b = field.try_into_interned(&mut interner).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&*a, "Forest");
+
+        interner.intern("Water");
+        assert_eq!(interner.len(), 2);
+
+        let null: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(null.try_into_interned_opt(&mut interner).unwrap(), None);
+    }
+}
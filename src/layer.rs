@@ -0,0 +1,414 @@
+use std::collections::HashSet;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use gdal::errors::GdalError;
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::{Geometry, Layer, LayerAccess, OGRwkbGeometryType};
+use gdal_sys::OGRLayerH;
+
+use crate::GdalTraitError;
+
+/// Metadata of the layer a [`LoadedLayer`] was read from.
+#[derive(Clone)]
+pub struct LayerMeta {
+    pub name: String,
+    pub srs: Option<SpatialRef>,
+    /// Path of the dataset, if read with [`crate::FromFeature::from_dataset_with_meta`], e.g. to
+    /// tell which file a feature came from after merging many loads.
+    pub source: Option<String>,
+}
+
+impl LayerMeta {
+    pub(crate) fn from_layer(layer: &Layer) -> Self {
+        Self {
+            name: layer.name(),
+            srs: layer.spatial_ref(),
+            source: None,
+        }
+    }
+}
+
+/// Features read from a layer, along with the layer's metadata.
+///
+/// Created by [`crate::FromFeature::from_layer_with_meta`] and
+/// [`crate::FromFeature::from_dataset_with_meta`].
+#[derive(Clone)]
+pub struct LoadedLayer<T> {
+    pub items: Vec<T>,
+    pub meta: LayerMeta,
+}
+
+impl<T> LoadedLayer<T> {
+    /// Split into the read features and the layer metadata.
+    pub fn into_parts(self) -> (Vec<T>, LayerMeta) {
+        (self.items, self.meta)
+    }
+}
+
+impl<T> IntoIterator for LoadedLayer<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LoadedLayer<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// The geometry type declared by a layer, e.g. `wkbMultiPolygon`, or `wkbUnknown` when it may
+/// hold any type.
+pub fn layer_geometry_type(layer: &Layer) -> OGRwkbGeometryType::Type {
+    unsafe { gdal_sys::OGR_L_GetGeomType(layer.c_layer()) }
+}
+
+/// The broad kind of geometries in a layer, e.g. for picking a renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeometryKind {
+    /// Points and multi-points.
+    Point,
+    /// Line strings and other curves, single or multi.
+    Line,
+    /// Polygons and other surfaces, single or multi.
+    Polygon,
+    /// Geometry collections of mixed types.
+    Collection,
+    /// Layers of any or no geometry type.
+    Unknown,
+}
+
+impl GeometryKind {
+    /// The kind of a geometry type, ignoring Z, M and whether it's a multi type.
+    pub fn from_geometry_type(geometry_type: OGRwkbGeometryType::Type) -> Self {
+        use OGRwkbGeometryType::*;
+
+        match unsafe { gdal_sys::OGR_GT_Flatten(geometry_type) } {
+            wkbPoint | wkbMultiPoint => GeometryKind::Point,
+            wkbLineString | wkbMultiLineString | wkbCircularString | wkbCompoundCurve
+            | wkbMultiCurve | wkbCurve => GeometryKind::Line,
+            wkbPolygon | wkbMultiPolygon | wkbCurvePolygon | wkbMultiSurface | wkbSurface
+            | wkbTriangle | wkbTIN | wkbPolyhedralSurface => GeometryKind::Polygon,
+            wkbGeometryCollection => GeometryKind::Collection,
+            _ => GeometryKind::Unknown,
+        }
+    }
+}
+
+/// The [`GeometryKind`] of a layer, see [`layer_geometry_type`].
+pub fn layer_geometry_kind(layer: &Layer) -> GeometryKind {
+    GeometryKind::from_geometry_type(layer_geometry_type(layer))
+}
+
+/// The name of a layer's default geometry field, e.g. `"geom"` in a GeoPackage, to tell which
+/// column the geometry passed to [`crate::FromFeature::read`] came from.
+///
+/// `None` for layers without geometry, and for unnamed geometry columns, e.g. of shapefiles.
+pub fn layer_geometry_field_name(layer: &Layer) -> Option<String> {
+    unsafe {
+        let ptr = gdal_sys::OGR_L_GetGeometryColumn(layer.c_layer());
+        if ptr.is_null() {
+            return None;
+        }
+        let name = CStr::from_ptr(ptr).to_string_lossy();
+        (!name.is_empty()).then(|| name.into_owned())
+    }
+}
+
+/// Read only the geometries of a layer, e.g. for tilers, skipping features without one.
+///
+/// All attribute fields and the style string are set as ignored for the read, so that drivers
+/// supporting it, e.g. shapefiles and GeoPackages, don't read them at all. Other drivers read
+/// them as usual, the result being the same.
+pub fn geometries_from_layer(layer: &mut Layer) -> Result<Vec<Geometry>, GdalTraitError> {
+    let fields: Vec<String> = layer.defn().fields().map(|field| field.name()).collect();
+    let _ignored = IgnoredFields::set(
+        layer,
+        fields.iter().map(String::as_str).chain(["OGR_STYLE"]),
+    )?;
+
+    Ok(layer
+        .features()
+        .filter_map(|feature| feature.geometry().cloned())
+        .collect())
+}
+
+/// Whether [`LayerAccess::feature_count`] is cheap for a layer, i.e. the driver knows the count
+/// without reading every feature.
+///
+/// Counting scans the whole layer for e.g. GeoJSON and CSV, or for shapefiles with an attribute
+/// filter, so check this before counting just to pre-allocate or show progress.
+pub fn layer_has_fast_count(layer: &Layer) -> bool {
+    layer_has_capability(layer, "FastFeatureCount")
+}
+
+/// Whether a layer has a spatial filter set.
+pub(crate) fn layer_has_spatial_filter(layer: &Layer) -> bool {
+    unsafe { !gdal_sys::OGR_L_GetSpatialFilter(layer.c_layer()).is_null() }
+}
+
+/// Whether a layer supports an OGR capability, e.g. `"RandomRead"`.
+pub(crate) fn layer_has_capability(layer: &Layer, capability: &str) -> bool {
+    let Ok(capability) = CString::new(capability) else {
+        return false;
+    };
+    unsafe { gdal_sys::OGR_L_TestCapability(layer.c_layer(), capability.as_ptr()) != 0 }
+}
+
+/// Has GDAL skip reading the given fields of a layer, until dropped.
+///
+/// Fields ignored beforehand, e.g. by the caller, stay ignored and are restored on drop.
+pub(crate) struct IgnoredFields {
+    c_layer: OGRLayerH,
+    previous: Vec<CString>,
+}
+
+impl IgnoredFields {
+    /// Ignore `fields`, which may include `"OGR_GEOMETRY"` and `"OGR_STYLE"`.
+    ///
+    /// Errors if a field doesn't exist in the layer.
+    pub(crate) fn set<'a>(
+        layer: &Layer,
+        fields: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, GdalTraitError> {
+        let fields = fields
+            .into_iter()
+            .map(CString::new)
+            .collect::<Result<Vec<CString>, _>>()
+            .map_err(GdalError::from)?;
+
+        Self::add(unsafe { layer.c_layer() }, fields)
+    }
+
+    /// Ignore all attribute fields of the layer except those at the `kept` indices.
+    pub(crate) fn keep_only(
+        layer: &Layer,
+        kept: impl IntoIterator<Item = usize>,
+    ) -> Result<Self, GdalTraitError> {
+        let kept: HashSet<usize> = kept.into_iter().collect();
+        let c_layer = unsafe { layer.c_layer() };
+
+        let fields = unsafe {
+            let c_defn = gdal_sys::OGR_L_GetLayerDefn(c_layer);
+            (0..gdal_sys::OGR_FD_GetFieldCount(c_defn))
+                .filter(|index| !kept.contains(&(*index as usize)))
+                .map(|index| {
+                    let c_field = gdal_sys::OGR_FD_GetFieldDefn(c_defn, index);
+                    CStr::from_ptr(gdal_sys::OGR_Fld_GetNameRef(c_field)).to_owned()
+                })
+                .collect()
+        };
+
+        Self::add(c_layer, fields)
+    }
+
+    fn add(c_layer: OGRLayerH, fields: Vec<CString>) -> Result<Self, GdalTraitError> {
+        let previous = unsafe { ignored_fields(c_layer) };
+        let mut ignored = previous.clone();
+        ignored.extend(fields.into_iter().filter(|field| !previous.contains(field)));
+
+        set_ignored_fields(c_layer, &ignored)?;
+        Ok(Self { c_layer, previous })
+    }
+}
+
+impl Drop for IgnoredFields {
+    fn drop(&mut self) {
+        let _ = set_ignored_fields(self.c_layer, &self.previous);
+    }
+}
+
+/// Restores the spatial filter and clears the attribute filter of a layer, for those flagged as
+/// set, when dropped.
+///
+/// GDAL offers no way to query the attribute filter of a layer, so an attribute filter which
+/// was replaced is cleared rather than restored.
+pub(crate) struct FilterGuard {
+    c_layer: OGRLayerH,
+    previous_spatial: Option<Geometry>,
+    pub(crate) spatial: bool,
+    pub(crate) attribute: bool,
+}
+
+impl FilterGuard {
+    /// A guard with neither filter flagged, keeping a copy of the current spatial filter.
+    pub(crate) fn new(layer: &Layer) -> Self {
+        let c_layer = unsafe { layer.c_layer() };
+        let previous_spatial = unsafe {
+            let c_filter = gdal_sys::OGR_L_GetSpatialFilter(c_layer);
+            (!c_filter.is_null())
+                .then(|| Geometry::with_c_geometry(gdal_sys::OGR_G_Clone(c_filter), true))
+        };
+
+        Self {
+            c_layer,
+            previous_spatial,
+            spatial: false,
+            attribute: false,
+        }
+    }
+}
+
+impl Drop for FilterGuard {
+    fn drop(&mut self) {
+        if self.spatial {
+            let c_filter = self
+                .previous_spatial
+                .as_ref()
+                .map_or(ptr::null_mut(), |filter| unsafe { filter.c_geometry() });
+            unsafe { gdal_sys::OGR_L_SetSpatialFilter(self.c_layer, c_filter) };
+        }
+        if self.attribute {
+            unsafe { gdal_sys::OGR_L_SetAttributeFilter(self.c_layer, ptr::null()) };
+        }
+    }
+}
+
+/// The fields currently ignored by a layer, in the form taken by `OGR_L_SetIgnoredFields`.
+unsafe fn ignored_fields(c_layer: OGRLayerH) -> Vec<CString> {
+    let c_defn = gdal_sys::OGR_L_GetLayerDefn(c_layer);
+    let mut ignored = Vec::new();
+
+    for index in 0..gdal_sys::OGR_FD_GetFieldCount(c_defn) {
+        let c_field = gdal_sys::OGR_FD_GetFieldDefn(c_defn, index);
+        if gdal_sys::OGR_Fld_IsIgnored(c_field) != 0 {
+            ignored.push(CStr::from_ptr(gdal_sys::OGR_Fld_GetNameRef(c_field)).to_owned());
+        }
+    }
+    // The first geometry field goes by "OGR_GEOMETRY", as it may be unnamed, e.g. in shapefiles.
+    if gdal_sys::OGR_FD_IsGeometryIgnored(c_defn) != 0 {
+        ignored.push(CString::new("OGR_GEOMETRY").unwrap());
+    }
+    for index in 1..gdal_sys::OGR_FD_GetGeomFieldCount(c_defn) {
+        let c_field = gdal_sys::OGR_FD_GetGeomFieldDefn(c_defn, index);
+        if gdal_sys::OGR_GFld_IsIgnored(c_field) != 0 {
+            ignored.push(CStr::from_ptr(gdal_sys::OGR_GFld_GetNameRef(c_field)).to_owned());
+        }
+    }
+    if gdal_sys::OGR_FD_IsStyleIgnored(c_defn) != 0 {
+        ignored.push(CString::new("OGR_STYLE").unwrap());
+    }
+
+    ignored
+}
+
+/// Replace the ignored fields of a layer, an empty `fields` clearing them.
+fn set_ignored_fields(c_layer: OGRLayerH, fields: &[CString]) -> Result<(), GdalTraitError> {
+    let mut field_ptrs: Vec<*const c_char> = fields
+        .iter()
+        .map(|field| field.as_ptr())
+        .chain([ptr::null()])
+        .collect();
+
+    let err = unsafe { gdal_sys::OGR_L_SetIgnoredFields(c_layer, field_ptrs.as_mut_ptr()) };
+    if err != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalError::OgrError {
+            err,
+            method_name: "OGR_L_SetIgnoredFields",
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use gdal::Dataset;
+
+    use super::*;
+
+    #[test]
+    fn test_layer_geometry_kind() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let layer = ds.layer(0).unwrap();
+        assert_eq!(layer_geometry_type(&layer), OGRwkbGeometryType::wkbPolygon);
+        assert_eq!(layer_geometry_kind(&layer), GeometryKind::Polygon);
+
+        let ds = Dataset::open("fixtures/Gävle_test_2_Geopackage_530541/Gävle_test_2_530541.gpkg")
+            .unwrap();
+        // Declared as generic GEOMETRY, despite only holding lines.
+        assert_eq!(
+            layer_geometry_kind(&ds.layer_by_name("Net_VAG_Link").unwrap()),
+            GeometryKind::Unknown
+        );
+
+        assert_eq!(
+            GeometryKind::from_geometry_type(OGRwkbGeometryType::wkbMultiLineStringZM),
+            GeometryKind::Line
+        );
+        assert_eq!(
+            GeometryKind::from_geometry_type(OGRwkbGeometryType::wkbMultiSurface),
+            GeometryKind::Polygon
+        );
+        assert_eq!(
+            GeometryKind::from_geometry_type(OGRwkbGeometryType::wkbGeometryCollection25D),
+            GeometryKind::Collection
+        );
+        assert_eq!(
+            GeometryKind::from_geometry_type(OGRwkbGeometryType::wkbNone),
+            GeometryKind::Unknown
+        );
+    }
+
+    #[test]
+    fn test_geometries_from_layer() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let geometries = geometries_from_layer(&mut layer).unwrap();
+        assert_eq!(geometries.len(), 177);
+        assert_eq!(
+            geometries[110].wkt().unwrap(),
+            layer
+                .feature(110)
+                .unwrap()
+                .geometry()
+                .unwrap()
+                .wkt()
+                .unwrap()
+        );
+
+        // The fields are read again afterwards.
+        let name_idx = layer.defn().field_index("NAME").unwrap();
+        let sweden = layer.feature(110).unwrap();
+        assert_eq!(
+            sweden.field_as_string(name_idx).unwrap().as_deref(),
+            Some("Sweden")
+        );
+    }
+
+    #[test]
+    fn test_layer_geometry_field_name() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        assert_eq!(layer_geometry_field_name(&ds.layer(0).unwrap()), None);
+
+        let ds = Dataset::open("fixtures/Gävle_test_2_Geopackage_530541/Gävle_test_2_530541.gpkg")
+            .unwrap();
+        assert_eq!(
+            layer_geometry_field_name(&ds.layer_by_name("Net_VAG_Link").unwrap()).as_deref(),
+            Some("geom")
+        );
+    }
+
+    #[test]
+    fn test_layer_has_fast_count() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+        assert!(layer_has_fast_count(&layer));
+
+        layer.set_attribute_filter("CONTINENT = 'Europe'").unwrap();
+        assert!(!layer_has_fast_count(&layer));
+    }
+}
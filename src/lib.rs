@@ -1,9 +1,64 @@
-pub use feature::{FieldResult, FromFeature};
+pub use dataset::{
+    vsicurl_path, vsizip_path, ConfigGuard, DatasetCredentials, DatasetLayer, FromDataset,
+};
+#[cfg(gdal_ge_3_7)]
+pub use feature::read_field_iso8601;
+#[cfg(feature = "chrono")]
+pub use feature::{date_from_fields, date_from_fields_opt};
+pub use feature::{
+    feature_native_data, feature_style, read_dynamic, require_fid, require_fid_as, FieldResult,
+    Fields, FromFeature, FromFeatureContext, LazyFields, TypedFeatures,
+};
+pub use format::{dump_feature, format_field_value};
+/// Derive macro for [`FromDataset`].
+pub use gdal_traits_derive::FromDataset;
+#[cfg(feature = "geo-types")]
+pub use geometry::GeoOrWkt;
+pub use geometry::GeometryExt;
+pub use intern::StringInterner;
+pub use layer::{
+    geometries_from_layer, layer_geometry_field_name, layer_geometry_kind, layer_geometry_type,
+    layer_has_fast_count, GeometryKind, LayerMeta, LoadedLayer,
+};
+pub use options::ReadOptions;
+pub use raster::sample_raster_at;
+pub use schema::{
+    create_layer, create_layer_with, layer_field_aliases, layer_field_flags, CreateLayerOptions,
+    FieldDefinition, FieldFlags, SchemaIssue,
+};
+pub use value::{FromFieldValue, NullSentinel};
+pub use write::{with_transaction, ToFeature};
+
+// Lets the derive macros refer to `::gdal_traits` from within this crate too.
+extern crate self as gdal_traits;
+
+use std::error::Error as StdError;
+use std::sync::Arc;
 
 use gdal::errors::GdalError;
+
+/// Items used by the derive macros, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::dataset::read_dataset_layer;
+    pub use gdal::Dataset;
+}
 use thiserror::Error;
 
+mod convert;
+mod dataset;
 mod feature;
+mod format;
+mod geometry;
+mod intern;
+mod layer;
+mod options;
+mod raster;
+mod schema;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+mod value;
+mod write;
 
 #[derive(Error, Debug, Clone)]
 pub enum GdalTraitError {
@@ -13,4 +68,235 @@ pub enum GdalTraitError {
     NullField,
     #[error("GDAL Trait error: Invalid FieldValue: {0}")]
     InvalidFieldValue(String),
+    /// An element of a list field failed to convert, at position `index`.
+    #[error("GDAL Trait error: Invalid list element {index}: {detail}")]
+    ListElementError { index: usize, detail: String },
+    #[error("GDAL Trait error: Failed to parse geometry: {0}")]
+    GeometryParse(String),
+    #[error("GDAL Trait error: Geometry operation failed: {0}")]
+    GeometryOperation(String),
+    #[error("GDAL Trait error: Unsupported geometry type: {0}")]
+    UnsupportedGeometry(String),
+    #[error("GDAL Trait error: No feature with FID {0}")]
+    FeatureNotFound(u64),
+    #[error("GDAL Trait error: Feature has no FID")]
+    NoFid,
+    /// The layer doesn't support a read, e.g. lacking random reads.
+    #[error("GDAL Trait error: Unsupported layer: {0}")]
+    UnsupportedLayer(String),
+    #[error("GDAL Trait error: Point ({x}, {y}) is outside the raster")]
+    RasterOutOfBounds { x: f64, y: f64 },
+    #[error("GDAL Trait error: Raster has no data at ({x}, {y})")]
+    RasterNoData { x: f64, y: f64 },
+    #[error("GDAL Trait error: Failed to open {path:?}: {source}")]
+    OpenFailed { path: String, source: GdalError },
+    #[error("{source} (layer: {layer:?}, FID: {fid:?})")]
+    WithContext {
+        fid: Option<u64>,
+        layer: Option<String>,
+        source: Box<GdalTraitError>,
+    },
+    /// Any other error, e.g. from a geometry library, shared so that `GdalTraitError` stays
+    /// `Clone` whatever the source type. See [`GdalTraitError::other`].
+    #[error(transparent)]
+    Other(Arc<dyn StdError + Send + Sync>),
+}
+
+impl PartialEq for GdalTraitError {
+    /// [`GdalTraitError::Other`] errors are equal only when they share the same source.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::GdalError(a), Self::GdalError(b)) => a == b,
+            (Self::NullField, Self::NullField) | (Self::NoFid, Self::NoFid) => true,
+            (Self::InvalidFieldValue(a), Self::InvalidFieldValue(b))
+            | (Self::GeometryParse(a), Self::GeometryParse(b))
+            | (Self::GeometryOperation(a), Self::GeometryOperation(b))
+            | (Self::UnsupportedGeometry(a), Self::UnsupportedGeometry(b))
+            | (Self::UnsupportedLayer(a), Self::UnsupportedLayer(b)) => a == b,
+            (
+                Self::ListElementError { index, detail },
+                Self::ListElementError {
+                    index: other_index,
+                    detail: other_detail,
+                },
+            ) => index == other_index && detail == other_detail,
+            (Self::FeatureNotFound(a), Self::FeatureNotFound(b)) => a == b,
+            (
+                Self::RasterOutOfBounds { x, y },
+                Self::RasterOutOfBounds {
+                    x: other_x,
+                    y: other_y,
+                },
+            )
+            | (
+                Self::RasterNoData { x, y },
+                Self::RasterNoData {
+                    x: other_x,
+                    y: other_y,
+                },
+            ) => x == other_x && y == other_y,
+            (
+                Self::OpenFailed { path, source },
+                Self::OpenFailed {
+                    path: other_path,
+                    source: other_source,
+                },
+            ) => path == other_path && source == other_source,
+            (
+                Self::WithContext { fid, layer, source },
+                Self::WithContext {
+                    fid: other_fid,
+                    layer: other_layer,
+                    source: other_source,
+                },
+            ) => fid == other_fid && layer == other_layer && source == other_source,
+            (Self::Other(a), Self::Other(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl GdalTraitError {
+    /// Wrap any error as a [`GdalTraitError::Other`], e.g. in `read` implementations:
+    ///
+    /// ```ignore
+    /// let code = code_field.try_into_string()?.parse::<u32>().map_err(GdalTraitError::other)?;
+    /// ```
+    pub fn other(e: impl StdError + Send + Sync + 'static) -> Self {
+        GdalTraitError::Other(Arc::new(e))
+    }
+
+    /// Attach the layer and feature FID which caused the error.
+    ///
+    /// Field errors from the `from_layer` family already carry this context.
+    pub fn with_context(self, layer: Option<&str>, fid: Option<u64>) -> Self {
+        GdalTraitError::WithContext {
+            fid,
+            layer: layer.map(str::to_string),
+            source: Box::new(self),
+        }
+    }
+
+    /// Whether a field was NULL, e.g. to substitute a default:
+    ///
+    /// ```ignore
+    /// let pop_year = match field.try_into_int() {
+    ///     Err(e) if e.is_null_field() => 2019,
+    ///     res => res?,
+    /// };
+    /// ```
+    ///
+    /// Sees through [`GdalTraitError::WithContext`].
+    pub fn is_null_field(&self) -> bool {
+        match self {
+            GdalTraitError::NullField => true,
+            GdalTraitError::WithContext { source, .. } => source.is_null_field(),
+            _ => false,
+        }
+    }
+
+    /// Whether a field value failed to convert, including elements of list fields.
+    ///
+    /// Sees through [`GdalTraitError::WithContext`].
+    pub fn is_invalid_value(&self) -> bool {
+        match self {
+            GdalTraitError::InvalidFieldValue(_) | GdalTraitError::ListElementError { .. } => true,
+            GdalTraitError::WithContext { source, .. } => source.is_invalid_value(),
+            _ => false,
+        }
+    }
+
+    /// The underlying [`GdalError`], e.g. to match on specific GDAL failures.
+    ///
+    /// Found through [`GdalTraitError::OpenFailed`] and [`GdalTraitError::WithContext`] too.
+    pub fn as_gdal_error(&self) -> Option<&GdalError> {
+        match self {
+            GdalTraitError::GdalError(e) | GdalTraitError::OpenFailed { source: e, .. } => Some(e),
+            GdalTraitError::WithContext { source, .. } => source.as_gdal_error(),
+            _ => None,
+        }
+    }
+
+    /// Take the underlying [`GdalError`], see [`GdalTraitError::as_gdal_error`].
+    pub fn into_gdal_error(self) -> Option<GdalError> {
+        match self {
+            GdalTraitError::GdalError(e) | GdalTraitError::OpenFailed { source: e, .. } => Some(e),
+            GdalTraitError::WithContext { source, .. } => source.into_gdal_error(),
+            _ => None,
+        }
+    }
+}
+
+impl From<GdalTraitError> for std::io::Error {
+    /// Maps to [`std::io::ErrorKind::InvalidData`], keeping the `GdalTraitError` as the source.
+    fn from(value: GdalTraitError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_io_error() {
+        let err: std::io::Error = GdalTraitError::NullField.into();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), GdalTraitError::NullField.to_string());
+    }
+
+    #[test]
+    fn test_other() {
+        let parse_error = "x".parse::<u32>().unwrap_err();
+        let err = GdalTraitError::other(parse_error.clone());
+        assert_eq!(err.to_string(), parse_error.to_string());
+
+        let GdalTraitError::Other(source) = &err else {
+            panic!("Expected an Other error");
+        };
+        assert_eq!(source.downcast_ref(), Some(&parse_error));
+
+        assert_eq!(err.clone(), err);
+        assert_ne!(err, GdalTraitError::other(parse_error));
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert!(GdalTraitError::NullField.is_null_field());
+        assert!(GdalTraitError::NullField
+            .with_context(Some("countries"), Some(110))
+            .is_null_field());
+        assert!(!GdalTraitError::NoFid.is_null_field());
+
+        assert!(GdalTraitError::InvalidFieldValue("1.5".to_string()).is_invalid_value());
+        assert!(GdalTraitError::ListElementError {
+            index: 1,
+            detail: "1.5".to_string(),
+        }
+        .with_context(None, Some(3))
+        .is_invalid_value());
+        assert!(!GdalTraitError::NullField.is_invalid_value());
+    }
+
+    #[test]
+    fn test_gdal_error() {
+        let gdal_error = GdalError::OgrError {
+            err: gdal_sys::OGRErr::OGRERR_FAILURE,
+            method_name: "OGR_L_CommitTransaction",
+        };
+
+        let err = GdalTraitError::from(gdal_error.clone()).with_context(Some("roads"), Some(3));
+        assert_eq!(err.as_gdal_error(), Some(&gdal_error));
+        assert_eq!(err.into_gdal_error(), Some(gdal_error.clone()));
+
+        let err = GdalTraitError::OpenFailed {
+            path: "roads.gpkg".to_string(),
+            source: gdal_error.clone(),
+        };
+        assert_eq!(err.as_gdal_error(), Some(&gdal_error));
+
+        assert_eq!(GdalTraitError::NullField.as_gdal_error(), None);
+        assert_eq!(GdalTraitError::NoFid.into_gdal_error(), None);
+    }
 }
@@ -0,0 +1,166 @@
+use gdal::spatial_ref::SpatialRef;
+
+/// Options for [`crate::FromFeature::from_layer_with`].
+///
+/// The default reads every feature unchanged, like [`crate::FromFeature::from_layer`].
+///
+/// With the `serde` feature, the options can be loaded from configuration files. All fields are
+/// optional, and `target_srs` is given as an EPSG code, e.g. `3006`, or any definition accepted
+/// by [`SpatialRef::from_definition`], e.g. WKT.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReadOptions {
+    /// Only read features intersecting `(min_x, min_y, max_x, max_y)`, in layer SRS units.
+    pub bbox: Option<(f64, f64, f64, f64)>,
+    /// Only read features matching an OGR SQL `WHERE` clause, e.g. `"POP_EST > 1000000"`.
+    pub attribute_filter: Option<String>,
+    /// Reproject geometries from the layer SRS into this SRS.
+    #[cfg_attr(feature = "serde", serde(with = "target_srs"))]
+    pub target_srs: Option<SpatialRef>,
+    /// Linearize curve geometries with this maximum angle step in degrees, 0 for GDAL's
+    /// default, see [`crate::GeometryExt::linearize`]. Applied before reprojection, e.g. for
+    /// cadastral layers with arcs which should be read into `geo_types`.
+    pub linearize: Option<f64>,
+    /// Simplify geometries with this tolerance, preserving topology. Applied after
+    /// reprojection, so it's given in target SRS units. Requires GDAL built with GEOS.
+    pub simplify_tolerance: Option<f64>,
+    /// Read at most this many features.
+    pub limit: Option<usize>,
+    /// Remove trailing whitespace of string fields, see
+    /// [`crate::FieldResult::try_into_string_trimmed`].
+    pub trim_strings: bool,
+    /// Skip features without geometry instead of passing `None` to `read`, e.g. for partially
+    /// digitized layers read into structs which require a geometry. Skipped features don't count
+    /// towards `limit`.
+    pub skip_null_geometry: bool,
+}
+
+impl ReadOptions {
+    pub fn with_bbox(mut self, bbox: (f64, f64, f64, f64)) -> Self {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    pub fn with_attribute_filter(mut self, filter: &str) -> Self {
+        self.attribute_filter = Some(filter.to_string());
+        self
+    }
+
+    pub fn with_target_srs(mut self, srs: SpatialRef) -> Self {
+        self.target_srs = Some(srs);
+        self
+    }
+
+    pub fn with_linearize(mut self, max_angle_step: f64) -> Self {
+        self.linearize = Some(max_angle_step);
+        self
+    }
+
+    pub fn with_simplify_tolerance(mut self, tolerance: f64) -> Self {
+        self.simplify_tolerance = Some(tolerance);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_trim_strings(mut self, trim_strings: bool) -> Self {
+        self.trim_strings = trim_strings;
+        self
+    }
+
+    pub fn with_skip_null_geometry(mut self, skip_null_geometry: bool) -> Self {
+        self.skip_null_geometry = skip_null_geometry;
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+mod target_srs {
+    use gdal::spatial_ref::{AxisMappingStrategy, SpatialRef};
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Srs {
+        Epsg(u32),
+        Definition(String),
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        srs: &Option<SpatialRef>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let srs = srs
+            .as_ref()
+            .map(|srs| match (srs.auth_name(), srs.auth_code()) {
+                (Ok(name), Ok(code)) if name == "EPSG" => Ok(Srs::Epsg(code as u32)),
+                _ => srs.to_wkt().map(Srs::Definition),
+            })
+            .transpose()
+            .map_err(ser::Error::custom)?;
+        srs.serialize(serializer)
+    }
+
+    /// Uses the traditional GIS axis order, like layer SRSs, so that e.g. X is the longitude.
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<SpatialRef>, D::Error> {
+        let Some(srs) = Option::<Srs>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+
+        let mut srs = match srs {
+            Srs::Epsg(code) => SpatialRef::from_epsg(code),
+            Srs::Definition(definition) => SpatialRef::from_definition(&definition),
+        }
+        .map_err(de::Error::custom)?;
+        srs.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        Ok(Some(srs))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let options: ReadOptions = serde_json::from_str(
+            r#"{
+                "bbox": [11.0, 55.0, 24.0, 69.0],
+                "attribute_filter": "POP_EST > 1000000",
+                "target_srs": 3006,
+                "limit": 10
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(options.bbox, Some((11.0, 55.0, 24.0, 69.0)));
+        assert_eq!(
+            options.attribute_filter.as_deref(),
+            Some("POP_EST > 1000000")
+        );
+        assert_eq!(
+            options.target_srs.as_ref().unwrap().auth_code().unwrap(),
+            3006
+        );
+        assert_eq!(options.limit, Some(10));
+        assert!(!options.trim_strings);
+        assert!(!options.skip_null_geometry);
+        assert!(options.linearize.is_none());
+
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(json["target_srs"], 3006);
+
+        let wkt = SpatialRef::from_epsg(4326).unwrap().to_wkt().unwrap();
+        let options: ReadOptions =
+            serde_json::from_value(serde_json::json!({ "target_srs": wkt })).unwrap();
+        assert_eq!(options.target_srs.unwrap().auth_code().unwrap(), 4326);
+
+        let options: ReadOptions = serde_json::from_str("{}").unwrap();
+        assert!(options.target_srs.is_none() && options.limit.is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use std::ffi::c_void;
+
+use gdal::errors::GdalError;
+use gdal::raster::RasterBand;
+use gdal::GeoTransform;
+
+use crate::GdalTraitError;
+
+/// The value of the pixel of `band` containing the point `(x, y)`, e.g. the elevation at a
+/// feature's location.
+///
+/// `geo_transform` is that of the band's dataset, and `(x, y)` must be in the raster SRS. Points
+/// outside the raster error with [`GdalTraitError::RasterOutOfBounds`], and pixels equal to the
+/// band's nodata value with [`GdalTraitError::RasterNoData`].
+///
+/// Typically used from [`crate::FromFeatureContext::read_ctx`], with the raster opened once in
+/// the context:
+///
+/// ```ignore
+/// impl FromFeatureContext<1, GdalTraitError> for Summit {
+///     const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME"];
+///
+///     type Context = (Dataset, GeoTransform);
+///
+///     fn make_context(_layer: &Layer) -> Result<Self::Context, GdalTraitError> {
+///         let dem = Dataset::open("dem.tif")?;
+///         let geo_transform = dem.geo_transform()?;
+///         Ok((dem, geo_transform))
+///     }
+///
+///     fn read_ctx(
+///         (dem, geo_transform): &Self::Context,
+///         _fid: Option<u64>,
+///         fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+///         geometry: Option<&Geometry>,
+///     ) -> Result<Self, GdalTraitError> {
+///         let [name_field] = fields;
+///         let (x, y, _) = geometry.ok_or(GdalTraitError::NullField)?.get_point(0);
+///
+///         Ok(Self {
+///             name: name_field.try_into_string()?,
+///             elevation: sample_raster_at(&dem.rasterband(1)?, geo_transform, x, y)?,
+///         })
+///     }
+/// }
+/// ```
+pub fn sample_raster_at(
+    band: &RasterBand,
+    geo_transform: &GeoTransform,
+    x: f64,
+    y: f64,
+) -> Result<f64, GdalTraitError> {
+    let mut inverse: GeoTransform = [0.0; 6];
+    let invertible = unsafe {
+        gdal_sys::GDALInvGeoTransform(geo_transform.as_ptr() as *mut f64, inverse.as_mut_ptr())
+    } != 0;
+    if !invertible {
+        return Err(GdalTraitError::GeometryOperation(format!(
+            "Geo transform {geo_transform:?} is not invertible"
+        )));
+    }
+
+    let column = (inverse[0] + x * inverse[1] + y * inverse[2]).floor();
+    let row = (inverse[3] + x * inverse[4] + y * inverse[5]).floor();
+    let (width, height) = band.size();
+    if !(0.0..width as f64).contains(&column) || !(0.0..height as f64).contains(&row) {
+        return Err(GdalTraitError::RasterOutOfBounds { x, y });
+    }
+
+    let mut value = 0.0f64;
+    let err = unsafe {
+        gdal_sys::GDALRasterIO(
+            band.c_rasterband(),
+            gdal_sys::GDALRWFlag::GF_Read,
+            column as i32,
+            row as i32,
+            1,
+            1,
+            &mut value as *mut f64 as *mut c_void,
+            1,
+            1,
+            gdal_sys::GDALDataType::GDT_Float64,
+            0,
+            0,
+        )
+    };
+    if err != gdal_sys::CPLErr::CE_None {
+        return Err(GdalError::CplError {
+            class: err,
+            number: 0,
+            msg: format!("Failed to read pixel ({column}, {row})"),
+        }
+        .into());
+    }
+
+    match band.no_data_value() {
+        Some(no_data) if value == no_data || (value.is_nan() && no_data.is_nan()) => {
+            Err(GdalTraitError::RasterNoData { x, y })
+        }
+        _ => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdal::vector::{Geometry, Layer, LayerAccess};
+    use gdal::{Dataset, DriverManager};
+
+    use super::*;
+    use crate::{FieldResult, FromFeatureContext, GeometryExt};
+
+    /// A 20x20 raster of one degree pixels over Scandinavia, with the value 100 + row.
+    fn elevation_raster() -> Dataset {
+        let driver = DriverManager::get_driver_by_name("MEM").unwrap();
+        let mut ds = driver
+            .create_with_band_type::<f64, _>("", 20, 20, 1)
+            .unwrap();
+        ds.set_geo_transform(&[10.0, 1.0, 0.0, 70.0, 0.0, -1.0])
+            .unwrap();
+
+        let band = ds.rasterband(1).unwrap();
+        for row in 0..20 {
+            let mut values = [100.0 + row as f64; 20];
+            unsafe {
+                gdal_sys::GDALRasterIO(
+                    band.c_rasterband(),
+                    gdal_sys::GDALRWFlag::GF_Write,
+                    0,
+                    row,
+                    20,
+                    1,
+                    values.as_mut_ptr() as *mut c_void,
+                    20,
+                    1,
+                    gdal_sys::GDALDataType::GDT_Float64,
+                    0,
+                    0,
+                );
+            }
+        }
+        ds
+    }
+
+    #[test]
+    fn test_sample_raster_at() {
+        let ds = elevation_raster();
+        let geo_transform = ds.geo_transform().unwrap();
+        let mut band = ds.rasterband(1).unwrap();
+
+        assert_eq!(
+            sample_raster_at(&band, &geo_transform, 18.07, 59.33).unwrap(),
+            110.0
+        );
+        assert_eq!(
+            sample_raster_at(&band, &geo_transform, 10.0, 70.0).unwrap(),
+            100.0
+        );
+        assert_eq!(
+            sample_raster_at(&band, &geo_transform, 9.99, 60.0),
+            Err(GdalTraitError::RasterOutOfBounds { x: 9.99, y: 60.0 })
+        );
+        assert!(sample_raster_at(&band, &geo_transform, 15.0, 50.0).is_err());
+
+        band.set_no_data_value(Some(110.0)).unwrap();
+        assert_eq!(
+            sample_raster_at(&band, &geo_transform, 18.07, 59.33),
+            Err(GdalTraitError::RasterNoData { x: 18.07, y: 59.33 })
+        );
+    }
+
+    struct Country {
+        name: String,
+        elevation: Option<f64>,
+    }
+
+    impl FromFeatureContext<1, GdalTraitError> for Country {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME"];
+
+        type Context = (Dataset, GeoTransform);
+
+        fn make_context(_layer: &Layer) -> Result<Self::Context, GdalTraitError> {
+            let raster = elevation_raster();
+            let geo_transform = raster.geo_transform()?;
+            Ok((raster, geo_transform))
+        }
+
+        fn read_ctx(
+            (raster, geo_transform): &Self::Context,
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            geometry: Option<&Geometry>,
+        ) -> Result<Self, GdalTraitError> {
+            let [name_field] = fields;
+            let (x, y, _) = geometry
+                .ok_or(GdalTraitError::NullField)?
+                .point_on_surface()?
+                .get_point(0);
+
+            let elevation = match sample_raster_at(&raster.rasterband(1)?, geo_transform, x, y) {
+                Ok(elevation) => Some(elevation),
+                Err(GdalTraitError::RasterOutOfBounds { .. }) => None,
+                Err(e) => return Err(e),
+            };
+
+            Ok(Self {
+                name: name_field.try_into_string()?,
+                elevation,
+            })
+        }
+    }
+
+    #[test]
+    fn test_sample_in_read_ctx() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let mut layer = ds.layer(0).unwrap();
+
+        let countries = Country::from_layer_ctx(&mut layer).unwrap();
+        let sweden = countries.iter().find(|c| c.name == "Sweden").unwrap();
+        assert!(sweden.elevation.is_some());
+        let fiji = countries.iter().find(|c| c.name == "Fiji").unwrap();
+        assert_eq!(fiji.elevation, None);
+    }
+}
@@ -0,0 +1,413 @@
+use std::ffi::CStr;
+
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::{FieldDefn, Layer, LayerAccess, LayerOptions, OGRFieldType, OGRwkbGeometryType};
+use gdal::Dataset;
+use thiserror::Error;
+
+use crate::GdalTraitError;
+
+/// A discrepancy between a struct's expected fields and the schema of a layer.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SchemaIssue {
+    #[error("Field {0:?} does not exist in the layer")]
+    MissingField(String),
+    #[error("Field {field:?} has type {}, expected {}", field_type_name(*.found), field_type_name(*.expected))]
+    TypeMismatch {
+        field: String,
+        expected: OGRFieldType::Type,
+        found: OGRFieldType::Type,
+    },
+}
+
+/// Definition of a field in an output layer, see [`create_layer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDefinition {
+    pub name: String,
+    pub field_type: OGRFieldType::Type,
+    /// Field width, e.g. the maximum length of a string. Uses the driver default if `None`.
+    ///
+    /// Some drivers, notably shapefile, enforce the width and silently truncate longer values.
+    pub width: Option<i32>,
+    /// Number of decimals of a real field. Uses the driver default if `None`.
+    pub precision: Option<i32>,
+}
+
+impl FieldDefinition {
+    /// A field definition with the driver's default width and precision.
+    pub fn new(name: &str, field_type: OGRFieldType::Type) -> Self {
+        Self {
+            name: name.to_string(),
+            field_type,
+            width: None,
+            precision: None,
+        }
+    }
+
+    pub fn with_width(mut self, width: i32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_precision(mut self, precision: i32) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+}
+
+/// Constraints of a field in an existing layer, see [`layer_field_flags`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldFlags {
+    pub name: String,
+    /// Whether the field may be NULL, i.e. the inverse of a NOT NULL constraint.
+    pub nullable: bool,
+    /// Whether the field has a UNIQUE constraint. Always `false` before GDAL 3.2.
+    pub unique: bool,
+    /// The default value as an SQL expression, e.g. `'text'`, `42` or `CURRENT_TIMESTAMP`.
+    pub default_value: Option<String>,
+}
+
+/// The constraints of all fields of a layer, in field order.
+///
+/// Useful for faithfully replicating a schema, as [`FieldDefinition`] only carries the types.
+/// Not all drivers report constraints, e.g. all shapefile fields are nullable.
+pub fn layer_field_flags(layer: &Layer) -> Vec<FieldFlags> {
+    unsafe {
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+
+        (0..gdal_sys::OGR_FD_GetFieldCount(c_defn))
+            .map(|i| {
+                let c_field = gdal_sys::OGR_FD_GetFieldDefn(c_defn, i);
+
+                let c_default = gdal_sys::OGR_Fld_GetDefault(c_field);
+                let default_value = (!c_default.is_null())
+                    .then(|| CStr::from_ptr(c_default).to_string_lossy().into_owned());
+
+                FieldFlags {
+                    name: CStr::from_ptr(gdal_sys::OGR_Fld_GetNameRef(c_field))
+                        .to_string_lossy()
+                        .into_owned(),
+                    nullable: gdal_sys::OGR_Fld_IsNullable(c_field) != 0,
+                    unique: field_is_unique(c_field),
+                    default_value,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(gdal_ge_3_2)]
+unsafe fn field_is_unique(c_field: gdal_sys::OGRFieldDefnH) -> bool {
+    gdal_sys::OGR_Fld_IsUnique(c_field) != 0
+}
+
+#[cfg(not(gdal_ge_3_2))]
+unsafe fn field_is_unique(_c_field: gdal_sys::OGRFieldDefnH) -> bool {
+    false
+}
+
+/// The name and alias, or alternative name, of every field of a layer, in field order.
+///
+/// Aliases are human-facing display names distinct from the physical column name, e.g. of
+/// FileGDB fields or the `name` of GeoPackage `gpkg_data_columns`. `None` for fields without
+/// one, for drivers without alias support, and always before GDAL 3.2. See
+/// [`crate::FromFeature::MATCH_ALIASES`] for reading fields by alias.
+pub fn layer_field_aliases(layer: &Layer) -> Vec<(String, Option<String>)> {
+    unsafe {
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+
+        (0..gdal_sys::OGR_FD_GetFieldCount(c_defn))
+            .map(|i| {
+                let c_field = gdal_sys::OGR_FD_GetFieldDefn(c_defn, i);
+                let name = CStr::from_ptr(gdal_sys::OGR_Fld_GetNameRef(c_field))
+                    .to_string_lossy()
+                    .into_owned();
+                (name, field_alias(c_field))
+            })
+            .collect()
+    }
+}
+
+/// The index of the field whose alias is `alias` in a feature definition, see
+/// [`layer_field_aliases`].
+pub(crate) unsafe fn alias_field_index(
+    c_defn: gdal_sys::OGRFeatureDefnH,
+    alias: &str,
+) -> Option<usize> {
+    (0..gdal_sys::OGR_FD_GetFieldCount(c_defn))
+        .find(|i| field_alias(gdal_sys::OGR_FD_GetFieldDefn(c_defn, *i)).as_deref() == Some(alias))
+        .map(|i| i as usize)
+}
+
+#[cfg(gdal_ge_3_2)]
+unsafe fn field_alias(c_field: gdal_sys::OGRFieldDefnH) -> Option<String> {
+    let c_alias = gdal_sys::OGR_Fld_GetAlternativeNameRef(c_field);
+    if c_alias.is_null() {
+        return None;
+    }
+    let alias = CStr::from_ptr(c_alias).to_string_lossy();
+    (!alias.is_empty()).then(|| alias.into_owned())
+}
+
+#[cfg(not(gdal_ge_3_2))]
+unsafe fn field_alias(_c_field: gdal_sys::OGRFieldDefnH) -> Option<String> {
+    None
+}
+
+/// Options for [`create_layer_with`], passed to the driver as layer creation options.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateLayerOptions {
+    /// Whether to build a spatial index on the geometry column, e.g. an RTree for GeoPackage or
+    /// SQLite, or a `.qix` file for shapefiles. Uses the driver default if `None`, which for
+    /// GeoPackage is to build one.
+    pub spatial_index: Option<bool>,
+}
+
+impl CreateLayerOptions {
+    pub fn with_spatial_index(mut self, spatial_index: bool) -> Self {
+        self.spatial_index = Some(spatial_index);
+        self
+    }
+}
+
+/// Create a new layer in `dataset` with the given fields.
+pub fn create_layer<'a>(
+    dataset: &'a mut Dataset,
+    name: &str,
+    srs: Option<&SpatialRef>,
+    geometry_type: OGRwkbGeometryType::Type,
+    fields: &[FieldDefinition],
+) -> Result<Layer<'a>, GdalTraitError> {
+    create_layer_with(
+        dataset,
+        name,
+        srs,
+        geometry_type,
+        fields,
+        &CreateLayerOptions::default(),
+    )
+}
+
+/// Create a new layer in `dataset` with the given fields, see [`CreateLayerOptions`].
+pub fn create_layer_with<'a>(
+    dataset: &'a mut Dataset,
+    name: &str,
+    srs: Option<&SpatialRef>,
+    geometry_type: OGRwkbGeometryType::Type,
+    fields: &[FieldDefinition],
+    options: &CreateLayerOptions,
+) -> Result<Layer<'a>, GdalTraitError> {
+    let mut creation_options = Vec::new();
+    if let Some(spatial_index) = options.spatial_index {
+        creation_options.push(if spatial_index {
+            "SPATIAL_INDEX=YES"
+        } else {
+            "SPATIAL_INDEX=NO"
+        });
+    }
+
+    let layer = dataset.create_layer(LayerOptions {
+        name,
+        srs,
+        ty: geometry_type,
+        options: (!creation_options.is_empty()).then_some(creation_options.as_slice()),
+    })?;
+
+    for field in fields {
+        let defn = FieldDefn::new(&field.name, field.field_type)?;
+        if let Some(width) = field.width {
+            defn.set_width(width);
+        }
+        if let Some(precision) = field.precision {
+            defn.set_precision(precision);
+        }
+        defn.add_to_layer(&layer)?;
+    }
+
+    Ok(layer)
+}
+
+/// Check that `fields` exist in `layer`, optionally with the given types.
+///
+/// With `match_aliases`, fields not found by name are looked up by alias.
+pub(crate) fn check_fields(
+    layer: &Layer,
+    fields: &[&str],
+    field_types: Option<&[OGRFieldType::Type]>,
+    match_aliases: bool,
+) -> Result<(), Vec<SchemaIssue>> {
+    let defn = layer.defn();
+    let c_defn = unsafe { gdal_sys::OGR_L_GetLayerDefn(layer.c_layer()) };
+
+    let issues: Vec<SchemaIssue> = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &name)| {
+            let by_alias = || {
+                match_aliases
+                    .then(|| unsafe { alias_field_index(c_defn, name) })
+                    .flatten()
+            };
+            let Some(idx) = defn.field_index(name).ok().or_else(by_alias) else {
+                return Some(SchemaIssue::MissingField(name.to_string()));
+            };
+
+            let expected = field_types?[i];
+            let found = defn.fields().nth(idx)?.field_type();
+            (expected != found).then(|| SchemaIssue::TypeMismatch {
+                field: name.to_string(),
+                expected,
+                found,
+            })
+        })
+        .collect();
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+/// Human readable name of an OGR field type, e.g. "String" or "Integer64".
+pub(crate) fn field_type_name(field_type: OGRFieldType::Type) -> String {
+    unsafe { CStr::from_ptr(gdal_sys::OGR_GetFieldTypeName(field_type)) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use gdal::DriverManager;
+
+    use super::*;
+
+    #[test]
+    fn test_spatial_index() {
+        let driver = DriverManager::get_driver_by_name("GPKG").unwrap();
+        let mut ds = driver
+            .create_vector_only("/vsimem/spatial_index.gpkg")
+            .unwrap();
+        let fields = [FieldDefinition::new("NAME", OGRFieldType::OFTString)];
+
+        create_layer_with(
+            &mut ds,
+            "indexed",
+            None,
+            OGRwkbGeometryType::wkbPoint,
+            &fields,
+            &CreateLayerOptions::default().with_spatial_index(true),
+        )
+        .unwrap();
+        create_layer_with(
+            &mut ds,
+            "unindexed",
+            None,
+            OGRwkbGeometryType::wkbPoint,
+            &fields,
+            &CreateLayerOptions::default().with_spatial_index(false),
+        )
+        .unwrap();
+
+        // The index may be built lazily, reopen to have it written.
+        drop(ds);
+        let ds = Dataset::open("/vsimem/spatial_index.gpkg").unwrap();
+        let mut result = ds
+            .execute_sql(
+                "SELECT name FROM sqlite_master \
+                 WHERE name IN ('rtree_indexed_geom', 'rtree_unindexed_geom')",
+                None,
+                gdal::vector::sql::Dialect::DEFAULT,
+            )
+            .unwrap()
+            .unwrap();
+        let rtree_tables: Vec<String> = result
+            .features()
+            .map(|feature| feature.field_as_string(0).unwrap().unwrap())
+            .collect();
+        assert_eq!(rtree_tables, vec!["rtree_indexed_geom".to_string()]);
+
+        drop(result);
+        drop(ds);
+        gdal::vsi::unlink_mem_file("/vsimem/spatial_index.gpkg").unwrap();
+    }
+
+    #[test]
+    fn test_layer_field_flags() {
+        let ds = Dataset::open("fixtures/ne_110m_admin_0_countries/ne_110m_admin_0_countries.shp")
+            .unwrap();
+        let layer = ds.layer(0).unwrap();
+
+        let flags = layer_field_flags(&layer);
+        assert_eq!(flags.len(), 168);
+        assert_eq!(
+            flags[0],
+            FieldFlags {
+                name: "featurecla".to_string(),
+                nullable: true,
+                unique: false,
+                default_value: None,
+            }
+        );
+
+        let driver = DriverManager::get_driver_by_name("Memory").unwrap();
+        let mut ds = driver.create_vector_only("").unwrap();
+        let layer = create_layer(&mut ds, "flags", None, OGRwkbGeometryType::wkbNone, &[]).unwrap();
+
+        let name = CString::new("STATUS").unwrap();
+        let default = CString::new("'active'").unwrap();
+        unsafe {
+            let c_field = gdal_sys::OGR_Fld_Create(name.as_ptr(), OGRFieldType::OFTString);
+            gdal_sys::OGR_Fld_SetNullable(c_field, 0);
+            gdal_sys::OGR_Fld_SetDefault(c_field, default.as_ptr());
+            gdal_sys::OGR_L_CreateField(layer.c_layer(), c_field, 1);
+            gdal_sys::OGR_Fld_Destroy(c_field);
+        }
+
+        assert_eq!(
+            layer_field_flags(&layer),
+            vec![FieldFlags {
+                name: "STATUS".to_string(),
+                nullable: false,
+                unique: false,
+                default_value: Some("'active'".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_create_layer() {
+        let driver = DriverManager::get_driver_by_name("Memory").unwrap();
+        let mut ds = driver.create_vector_only("").unwrap();
+
+        let fields = [
+            FieldDefinition::new("NAME", OGRFieldType::OFTString).with_width(10),
+            FieldDefinition::new("POP_EST", OGRFieldType::OFTReal)
+                .with_width(12)
+                .with_precision(1),
+        ];
+        let layer = create_layer(
+            &mut ds,
+            "countries",
+            None,
+            OGRwkbGeometryType::wkbPolygon,
+            &fields,
+        )
+        .unwrap();
+
+        let created: Vec<(String, OGRFieldType::Type, i32, i32)> = layer
+            .defn()
+            .fields()
+            .map(|f| (f.name(), f.field_type(), f.width(), f.precision()))
+            .collect();
+        assert_eq!(
+            created,
+            vec![
+                ("NAME".to_string(), OGRFieldType::OFTString, 10, 0),
+                ("POP_EST".to_string(), OGRFieldType::OFTReal, 12, 1),
+            ]
+        );
+    }
+}
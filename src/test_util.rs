@@ -0,0 +1,184 @@
+//! Helpers for testing code built on this crate, without fixture files.
+
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::{Feature, FieldValue, Geometry, LayerAccess, OGRFieldType, OGRwkbGeometryType};
+use gdal::{Dataset, DriverManager};
+
+use crate::{create_layer, FieldDefinition, GdalTraitError};
+
+/// Builds an in-memory dataset with a single layer, e.g. for exercising
+/// [`crate::FromFeature::from_layer`] in tests:
+///
+/// ```ignore
+/// let ds = MemDatasetBuilder::new("countries")
+///     .with_field("NAME", OGRFieldType::OFTString)
+///     .with_field("POP_EST", OGRFieldType::OFTReal)
+///     .with_row(vec![Some(FieldValue::StringValue("Sweden".into())), None], Some("POINT (18 59)"))
+///     .build()?;
+/// let countries = Country::from_layer(&mut ds.layer(0)?)?;
+/// ```
+pub struct MemDatasetBuilder {
+    layer_name: String,
+    srs: Option<SpatialRef>,
+    geometry_type: OGRwkbGeometryType::Type,
+    fields: Vec<FieldDefinition>,
+    rows: Vec<(Vec<Option<FieldValue>>, Option<String>)>,
+}
+
+impl MemDatasetBuilder {
+    /// A builder for a layer named `layer_name`, with unknown geometry type and no SRS.
+    pub fn new(layer_name: &str) -> Self {
+        Self {
+            layer_name: layer_name.to_string(),
+            srs: None,
+            geometry_type: OGRwkbGeometryType::wkbUnknown,
+            fields: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn with_srs(mut self, srs: SpatialRef) -> Self {
+        self.srs = Some(srs);
+        self
+    }
+
+    pub fn with_geometry_type(mut self, geometry_type: OGRwkbGeometryType::Type) -> Self {
+        self.geometry_type = geometry_type;
+        self
+    }
+
+    pub fn with_field(self, name: &str, field_type: OGRFieldType::Type) -> Self {
+        self.with_field_definition(FieldDefinition::new(name, field_type))
+    }
+
+    pub fn with_field_definition(mut self, field: FieldDefinition) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Add a feature with `values` for the fields in order, `None` being NULL, and a geometry
+    /// given as WKT.
+    pub fn with_row(mut self, values: Vec<Option<FieldValue>>, wkt: Option<&str>) -> Self {
+        self.rows.push((values, wkt.map(str::to_string)));
+        self
+    }
+
+    /// Create the dataset.
+    ///
+    /// Errors if a row has more values than there are fields, or on invalid WKT.
+    pub fn build(self) -> Result<Dataset, GdalTraitError> {
+        let driver = DriverManager::get_driver_by_name("Memory")?;
+        let mut dataset = driver.create_vector_only("")?;
+
+        {
+            let layer = create_layer(
+                &mut dataset,
+                &self.layer_name,
+                self.srs.as_ref(),
+                self.geometry_type,
+                &self.fields,
+            )?;
+
+            for (values, wkt) in &self.rows {
+                if values.len() > self.fields.len() {
+                    return Err(GdalTraitError::InvalidFieldValue(format!(
+                        "Row has {} values for {} fields",
+                        values.len(),
+                        self.fields.len()
+                    )));
+                }
+
+                let mut feature = Feature::new(layer.defn())?;
+                for (index, value) in values.iter().enumerate() {
+                    match value {
+                        Some(value) => feature.set_field(index, value)?,
+                        None => feature.set_field_null(index)?,
+                    }
+                }
+                if let Some(wkt) = wkt {
+                    let mut geometry = Geometry::from_wkt(wkt).map_err(|e| {
+                        GdalTraitError::GeometryParse(format!("Invalid WKT {wkt:?}: {e}"))
+                    })?;
+                    if let Some(srs) = &self.srs {
+                        geometry.set_spatial_ref(srs.clone());
+                    }
+                    feature.set_geometry(geometry)?;
+                }
+                feature.create(&layer)?;
+            }
+        }
+
+        Ok(dataset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FieldResult, FromFeature};
+
+    struct Country {
+        name: String,
+        pop_est: Option<f64>,
+        has_geometry: bool,
+    }
+
+    impl FromFeature<2, GdalTraitError> for Country {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME", "POP_EST"];
+
+        fn read(
+            _fid: Option<u64>,
+            fields: [FieldResult<GdalTraitError>; Self::NUM_FIELDS],
+            geometry: Option<&Geometry>,
+        ) -> Result<Self, GdalTraitError> {
+            let [name_field, pop_est_field] = fields;
+
+            Ok(Self {
+                name: name_field.try_into_string()?,
+                pop_est: pop_est_field.try_into_real_opt()?,
+                has_geometry: geometry.is_some(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_mem_dataset_builder() {
+        let ds = MemDatasetBuilder::new("countries")
+            .with_srs(SpatialRef::from_epsg(4326).unwrap())
+            .with_geometry_type(OGRwkbGeometryType::wkbPoint)
+            .with_field("NAME", OGRFieldType::OFTString)
+            .with_field("POP_EST", OGRFieldType::OFTReal)
+            .with_row(
+                vec![
+                    Some(FieldValue::StringValue("Sweden".to_string())),
+                    Some(FieldValue::RealValue(10285453.0)),
+                ],
+                Some("POINT (18 59)"),
+            )
+            .with_row(
+                vec![Some(FieldValue::StringValue("Atlantis".to_string())), None],
+                None,
+            )
+            .build()
+            .unwrap();
+
+        let mut layer = ds.layer(0).unwrap();
+        assert_eq!(layer.name(), "countries");
+
+        let countries = Country::from_layer(&mut layer).unwrap();
+        assert_eq!(countries.len(), 2);
+        assert_eq!(countries[0].name, "Sweden");
+        assert_eq!(countries[0].pop_est, Some(10285453.0));
+        assert!(countries[0].has_geometry);
+        assert_eq!(countries[1].pop_est, None);
+        assert!(!countries[1].has_geometry);
+
+        let too_many = MemDatasetBuilder::new("empty")
+            .with_row(vec![None], None)
+            .build();
+        assert!(matches!(
+            too_many,
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+    }
+}
@@ -0,0 +1,265 @@
+use gdal::vector::FieldValue;
+
+use crate::{FieldResult, GdalTraitError};
+
+/// Conversion of a single [`FieldValue`] into a Rust type.
+///
+/// The generic counterpart of the `try_into_*` conversions of [`FieldResult`], e.g. used for the
+/// elements of list fields by [`FieldResult::try_into_vec_of`].
+pub trait FromFieldValue: Sized {
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError>;
+}
+
+fn invalid(value: &FieldValue, type_name: &str) -> GdalTraitError {
+    GdalTraitError::InvalidFieldValue(format!("Failed to convert {value:?} into a {type_name}"))
+}
+
+impl FromFieldValue for i32 {
+    /// Also accepts 64-bit integers within range.
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::IntegerValue(v) => Ok(v),
+            FieldValue::Integer64Value(v) => v.try_into().map_err(|_| invalid(&value, "i32")),
+            value => Err(invalid(&value, "i32")),
+        }
+    }
+}
+
+impl FromFieldValue for i64 {
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::IntegerValue(v) => Ok(v.into()),
+            FieldValue::Integer64Value(v) => Ok(v),
+            value => Err(invalid(&value, "i64")),
+        }
+    }
+}
+
+impl FromFieldValue for f64 {
+    /// Also accepts 32-bit integers, which are exactly representable.
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::RealValue(v) => Ok(v),
+            FieldValue::IntegerValue(v) => Ok(v.into()),
+            value => Err(invalid(&value, "f64")),
+        }
+    }
+}
+
+impl FromFieldValue for String {
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::StringValue(v) => Ok(v),
+            value => Err(invalid(&value, "String")),
+        }
+    }
+}
+
+impl FromFieldValue for bool {
+    /// Accepts `0` and `1`, see [`FieldResult::try_into_bool`].
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::IntegerValue(0) => Ok(false),
+            FieldValue::IntegerValue(1) => Ok(true),
+            value => Err(invalid(&value, "bool")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromFieldValue for chrono::NaiveDate {
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::DateValue(v) => Ok(v),
+            value => Err(invalid(&value, "NaiveDate")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromFieldValue for chrono::DateTime<chrono::FixedOffset> {
+    fn from_field_value(value: FieldValue) -> Result<Self, GdalTraitError> {
+        match value {
+            FieldValue::DateTimeValue(v) => Ok(v),
+            value => Err(invalid(&value, "DateTime<FixedOffset>")),
+        }
+    }
+}
+
+/// A value which stands for NULL in legacy data, e.g. `-9999` or an empty string, see
+/// [`crate::FromFeature::NULL_SENTINELS`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullSentinel {
+    /// Matches both 32 and 64-bit integer fields.
+    Integer(i64),
+    /// Matches real fields, compared exactly.
+    Real(f64),
+    String(&'static str),
+}
+
+impl NullSentinel {
+    /// Whether `value` is this sentinel.
+    pub fn matches(&self, value: &FieldValue) -> bool {
+        match (self, value) {
+            (NullSentinel::Integer(s), FieldValue::IntegerValue(v)) => i64::from(*v) == *s,
+            (NullSentinel::Integer(s), FieldValue::Integer64Value(v)) => v == s,
+            (NullSentinel::Real(s), FieldValue::RealValue(v)) => v == s,
+            (NullSentinel::String(s), FieldValue::StringValue(v)) => v == s,
+            _ => false,
+        }
+    }
+}
+
+impl FieldResult<GdalTraitError> {
+    /// Convert a `FieldResult` into any [`FromFieldValue`] type, a single entry point for code
+    /// which is generic over field types.
+    ///
+    /// NULL fields are [`GdalTraitError::NullField`] errors, see
+    /// [`FieldResult::into_typed_opt`].
+    pub fn into_typed<T: FromFieldValue>(self) -> Result<T, GdalTraitError> {
+        T::from_field_value(self.into_res()?)
+    }
+
+    /// Convert a `FieldResult` into an Option<...> of any [`FromFieldValue`] type, NULL being
+    /// `None`.
+    pub fn into_typed_opt<T: FromFieldValue>(self) -> Result<Option<T>, GdalTraitError> {
+        self.into_opt_res()?.map(T::from_field_value).transpose()
+    }
+
+    /// Attempt to convert a list `FieldResult` into a `Vec<T>`, converting each element through
+    /// [`FromFieldValue`].
+    ///
+    /// Accepts integer, 64-bit integer, real and string lists. Errors with
+    /// [`GdalTraitError::ListElementError`] on the first element which fails to convert, e.g.
+    /// an out of range element when narrowing 64-bit integers into `i32`.
+    pub fn try_into_vec_of<T: FromFieldValue>(&self) -> Result<Vec<T>, GdalTraitError> {
+        let elements: Vec<FieldValue> = match self.to_owned().into_res()? {
+            FieldValue::IntegerListValue(list) => {
+                list.into_iter().map(FieldValue::IntegerValue).collect()
+            }
+            FieldValue::Integer64ListValue(list) => {
+                list.into_iter().map(FieldValue::Integer64Value).collect()
+            }
+            FieldValue::RealListValue(list) => {
+                list.into_iter().map(FieldValue::RealValue).collect()
+            }
+            FieldValue::StringListValue(list) => {
+                list.into_iter().map(FieldValue::StringValue).collect()
+            }
+            value => {
+                return Err(GdalTraitError::InvalidFieldValue(format!(
+                    "Failed to convert {value:?} into a list"
+                )))
+            }
+        };
+
+        elements
+            .into_iter()
+            .enumerate()
+            .map(|(index, element)| {
+                T::from_field_value(element).map_err(|e| GdalTraitError::ListElementError {
+                    index,
+                    detail: match e {
+                        GdalTraitError::InvalidFieldValue(msg) => msg,
+                        e => e.to_string(),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Attempt to convert a list `FieldResult` into an Option<`Vec<T>`>.
+    pub fn try_into_vec_of_opt<T: FromFieldValue>(&self) -> Result<Option<Vec<T>>, GdalTraitError> {
+        match self {
+            FieldResult::Null => Ok(None),
+            _ => self.try_into_vec_of().map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_sentinel() {
+        assert!(NullSentinel::Integer(-9999).matches(&FieldValue::IntegerValue(-9999)));
+        assert!(NullSentinel::Integer(-9999).matches(&FieldValue::Integer64Value(-9999)));
+        assert!(!NullSentinel::Integer(-9999).matches(&FieldValue::RealValue(-9999.0)));
+        assert!(NullSentinel::Real(-1.0).matches(&FieldValue::RealValue(-1.0)));
+        assert!(NullSentinel::String("").matches(&FieldValue::StringValue(String::new())));
+        assert!(!NullSentinel::String("").matches(&FieldValue::StringValue(" ".to_string())));
+    }
+
+    #[test]
+    fn test_into_typed() {
+        fn read_opt<T: FromFieldValue>(value: Option<FieldValue>) -> Option<T> {
+            let field: FieldResult<GdalTraitError> = match value {
+                Some(value) => FieldResult::Some(value),
+                None => FieldResult::Null,
+            };
+            field.into_typed_opt().unwrap()
+        }
+
+        assert_eq!(read_opt::<i64>(Some(FieldValue::IntegerValue(3))), Some(3));
+        assert_eq!(read_opt::<f64>(Some(FieldValue::RealValue(1.5))), Some(1.5));
+        assert_eq!(
+            read_opt::<bool>(Some(FieldValue::IntegerValue(1))),
+            Some(true)
+        );
+        assert_eq!(read_opt::<String>(None), None);
+
+        let field: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringValue("Gävle".to_string()));
+        assert_eq!(field.clone().into_typed::<String>().unwrap(), "Gävle");
+        assert!(matches!(
+            field.into_typed_opt::<i32>(),
+            Err(GdalTraitError::InvalidFieldValue(_))
+        ));
+
+        let null: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert!(matches!(
+            null.into_typed::<i32>(),
+            Err(GdalTraitError::NullField)
+        ));
+    }
+
+    #[test]
+    fn test_try_into_vec_of() {
+        let ints: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::IntegerListValue(vec![1, 2, 3]));
+        assert_eq!(ints.try_into_vec_of::<i64>().unwrap(), vec![1, 2, 3]);
+        assert_eq!(ints.try_into_vec_of::<f64>().unwrap(), vec![1.0, 2.0, 3.0]);
+        assert!(ints.try_into_vec_of::<String>().is_err());
+
+        let big: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::Integer64ListValue(vec![1, i64::MAX]));
+        let Err(GdalTraitError::ListElementError { index, detail }) = big.try_into_vec_of::<i32>()
+        else {
+            panic!("Expected an out of range element");
+        };
+        assert_eq!(index, 1);
+        assert!(detail.contains("i32"));
+
+        let strings: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::StringListValue(vec![
+                "a".to_string(),
+                "b".to_string(),
+            ]));
+        assert_eq!(
+            strings.try_into_vec_of::<String>().unwrap(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+
+        let not_a_list: FieldResult<GdalTraitError> =
+            FieldResult::Some(FieldValue::IntegerValue(1));
+        assert!(not_a_list.try_into_vec_of::<i32>().is_err());
+
+        let null: FieldResult<GdalTraitError> = FieldResult::Null;
+        assert_eq!(null.try_into_vec_of_opt::<i32>().unwrap(), None);
+        assert!(matches!(
+            null.try_into_vec_of::<i32>(),
+            Err(GdalTraitError::NullField)
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+use std::error::Error;
+use std::mem;
+
+use gdal::errors::GdalError;
+use gdal::vector::{Feature, FieldValue, Geometry, Layer, LayerAccess};
+use gdal_sys::OGRLayerH;
+
+use crate::GdalTraitError;
+
+/// The write counterpart of [`crate::FromFeature`].
+pub trait ToFeature<const N: usize, E>
+where
+    Self: Sized,
+    E: Error + From<GdalTraitError>,
+{
+    const NUM_FIELDS: usize = N;
+
+    /// Fields of the output layer, which must already exist.
+    const FIELDS: [&'static str; N];
+
+    /// 'Write' the values of [`ToFeature::FIELDS`], `None` being written as NULL.
+    fn write_fields(&self) -> Result<[Option<FieldValue>; N], E>;
+
+    /// 'Write' the geometry, if any.
+    fn write_geometry(&self) -> Result<Option<Geometry>, E> {
+        Ok(None)
+    }
+
+    /// Write all `items` as new features of a vector [`Layer`], returning the number written.
+    ///
+    /// The writes are wrapped in a layer transaction, which is rolled back on the first error,
+    /// see [`with_transaction`]. For drivers without transaction support, features written
+    /// before an error are kept.
+    fn to_layer<I: IntoIterator<Item = Self>>(items: I, layer: &mut Layer) -> Result<usize, E> {
+        let indices = Self::FIELDS
+            .iter()
+            .map(|fname| layer.defn().field_index(fname))
+            .collect::<Result<Vec<usize>, GdalError>>()
+            .map_err(GdalTraitError::from)?;
+
+        with_transaction(layer, |layer| write_items(items, layer, &indices))
+    }
+}
+
+/// Run `f` within a layer transaction, committing if it returns `Ok` and rolling back if it
+/// returns `Err` or panics.
+///
+/// For drivers without transaction support, `f` runs as is and its changes are kept even on
+/// errors. The commit failing is reported as a [`GdalTraitError::GdalError`].
+///
+/// ```ignore
+/// with_transaction(&mut layer, |layer| {
+///     let mut feature = layer.feature(fid).ok_or(GdalTraitError::FeatureNotFound(fid))?;
+///     feature.set_field_string(name_index, "Gävle")?;
+///     layer.set_feature(feature)?;
+///     Ok(())
+/// })?;
+/// ```
+pub fn with_transaction<R, E, F>(layer: &mut Layer, f: F) -> Result<R, E>
+where
+    E: From<GdalTraitError>,
+    F: FnOnce(&mut Layer) -> Result<R, E>,
+{
+    let c_layer = unsafe { layer.c_layer() };
+    let started =
+        unsafe { gdal_sys::OGR_L_StartTransaction(c_layer) } == gdal_sys::OGRErr::OGRERR_NONE;
+    if !started {
+        return f(layer);
+    }
+
+    // Rolls back unless committed, including when `f` panics.
+    let transaction = Transaction { c_layer };
+    let res = f(layer)?;
+
+    mem::forget(transaction);
+    let err = unsafe { gdal_sys::OGR_L_CommitTransaction(c_layer) };
+    if err != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalTraitError::from(GdalError::OgrError {
+            err,
+            method_name: "OGR_L_CommitTransaction",
+        })
+        .into());
+    }
+
+    Ok(res)
+}
+
+struct Transaction {
+    c_layer: OGRLayerH,
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        unsafe { gdal_sys::OGR_L_RollbackTransaction(self.c_layer) };
+    }
+}
+
+fn write_items<const N: usize, T, E, I>(
+    items: I,
+    layer: &Layer,
+    indices: &[usize],
+) -> Result<usize, E>
+where
+    T: ToFeature<N, E>,
+    E: Error + From<GdalTraitError>,
+    I: IntoIterator<Item = T>,
+{
+    let mut count = 0;
+    for item in items {
+        let mut feature = Feature::new(layer.defn()).map_err(GdalTraitError::from)?;
+
+        for (index, value) in indices.iter().zip(item.write_fields()?) {
+            match value {
+                Some(value) => feature.set_field(*index, &value),
+                None => feature.set_field_null(*index),
+            }
+            .map_err(GdalTraitError::from)?;
+        }
+        if let Some(geometry) = item.write_geometry()? {
+            feature
+                .set_geometry(geometry)
+                .map_err(GdalTraitError::from)?;
+        }
+
+        feature.create(layer).map_err(GdalTraitError::from)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use gdal::vector::{OGRFieldType, OGRwkbGeometryType};
+    use gdal::DriverManager;
+
+    use super::*;
+    use crate::{create_layer, FieldDefinition};
+
+    #[derive(Clone)]
+    struct City {
+        name: String,
+        population: Option<i32>,
+        location: (f64, f64),
+    }
+
+    impl ToFeature<2, GdalTraitError> for City {
+        const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NAME", "POPULATION"];
+
+        fn write_fields(&self) -> Result<[Option<FieldValue>; Self::NUM_FIELDS], GdalTraitError> {
+            Ok([
+                Some(FieldValue::StringValue(self.name.clone())),
+                self.population.map(FieldValue::IntegerValue),
+            ])
+        }
+
+        fn write_geometry(&self) -> Result<Option<Geometry>, GdalTraitError> {
+            let (x, y) = self.location;
+            Ok(Some(Geometry::from_wkt(&format!("POINT ({x} {y})"))?))
+        }
+    }
+
+    #[test]
+    fn test_to_layer() {
+        let driver = DriverManager::get_driver_by_name("Memory").unwrap();
+        let mut ds = driver.create_vector_only("").unwrap();
+        let mut layer = create_layer(
+            &mut ds,
+            "cities",
+            None,
+            OGRwkbGeometryType::wkbPoint,
+            &[
+                FieldDefinition::new("NAME", OGRFieldType::OFTString),
+                FieldDefinition::new("POPULATION", OGRFieldType::OFTInteger),
+            ],
+        )
+        .unwrap();
+
+        let cities = vec![
+            City {
+                name: "Stockholm".to_string(),
+                population: Some(984748),
+                location: (18.07, 59.33),
+            },
+            City {
+                name: "Gävle".to_string(),
+                population: None,
+                location: (17.14, 60.67),
+            },
+        ];
+
+        assert_eq!(City::to_layer(cities.clone(), &mut layer).unwrap(), 2);
+
+        let written: Vec<(String, Option<i32>, (f64, f64, f64))> = layer
+            .features()
+            .map(|feature| {
+                (
+                    feature.field_as_string(0).unwrap().unwrap(),
+                    feature.field_as_integer(1).unwrap(),
+                    feature.geometry().unwrap().get_point(0),
+                )
+            })
+            .collect();
+        assert_eq!(
+            written,
+            vec![
+                ("Stockholm".to_string(), Some(984748), (18.07, 59.33, 0.0)),
+                ("Gävle".to_string(), None, (17.14, 60.67, 0.0)),
+            ]
+        );
+
+        // Unknown fields are rejected before anything is written.
+        struct Unknown;
+        impl ToFeature<1, GdalTraitError> for Unknown {
+            const FIELDS: [&'static str; Self::NUM_FIELDS] = ["NOT_A_FIELD"];
+
+            fn write_fields(
+                &self,
+            ) -> Result<[Option<FieldValue>; Self::NUM_FIELDS], GdalTraitError> {
+                Ok([None])
+            }
+        }
+        assert!(Unknown::to_layer([Unknown], &mut layer).is_err());
+        assert_eq!(layer.feature_count(), 2);
+    }
+
+    #[test]
+    fn test_with_transaction() {
+        let driver = DriverManager::get_driver_by_name("GPKG").unwrap();
+        let mut ds = driver
+            .create_vector_only("/vsimem/with_transaction.gpkg")
+            .unwrap();
+        let mut layer = create_layer(
+            &mut ds,
+            "cities",
+            None,
+            OGRwkbGeometryType::wkbPoint,
+            &[FieldDefinition::new("NAME", OGRFieldType::OFTString)],
+        )
+        .unwrap();
+
+        let add_city = |layer: &mut Layer| -> Result<(), GdalTraitError> {
+            let mut feature = Feature::new(layer.defn())?;
+            feature.set_field_string(0, "Gävle")?;
+            feature.create(&*layer)?;
+            Ok(())
+        };
+
+        assert_eq!(with_transaction(&mut layer, add_city), Ok(()));
+        assert_eq!(layer.feature_count(), 1);
+
+        let res: Result<(), GdalTraitError> = with_transaction(&mut layer, |layer| {
+            add_city(layer)?;
+            Err(GdalTraitError::NoFid)
+        });
+        assert_eq!(res, Err(GdalTraitError::NoFid));
+        assert_eq!(layer.feature_count(), 1);
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _: Result<(), GdalTraitError> = with_transaction(&mut layer, |layer| {
+                add_city(layer)?;
+                panic!("Edit failed");
+            });
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(layer.feature_count(), 1);
+
+        drop(layer);
+        drop(ds);
+        gdal::vsi::unlink_mem_file("/vsimem/with_transaction.gpkg").unwrap();
+    }
+}